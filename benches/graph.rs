@@ -0,0 +1,125 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use pheap::graph::{
+    generate::{erdos_renyi, grid_2d, random_geometric},
+    DijkstraBuffer, SimpleGraph,
+};
+
+/// Creates a square grid graph with ```side * side``` nodes.
+fn grid(side: usize) -> SimpleGraph<u32> {
+    grid_2d(side, side, |node, _| {
+        let (row, col) = (node / side, node % side);
+        ((row * 31 + col * 17) % 13 + 1) as u32
+    })
+}
+
+fn repeated_dijkstra(c: &mut Criterion) {
+    let side = 100;
+    let g = grid(side);
+    let dest = [side * side - 1];
+
+    c.bench_function("Dijkstra (100 queries)", |b| {
+        b.iter(|| {
+            for src in 0..100 {
+                let _ = g.sssp_dijkstra(src, &dest);
+            }
+        })
+    });
+
+    c.bench_function("Dijkstra with buffer (100 queries)", |b| {
+        let mut buffer = DijkstraBuffer::with_capacity(side * side);
+        b.iter(|| {
+            for src in 0..100 {
+                let _ = g.sssp_dijkstra_with_buffer(src, &dest, &mut buffer);
+            }
+        })
+    });
+}
+
+fn random_graphs(c: &mut Criterion) {
+    let n_nodes = 100_000;
+    let g = erdos_renyi::<u32>(n_nodes, 4.0 / n_nodes as f64, 1..100, 42);
+
+    c.bench_function("Dijkstra lazy (Erdos-Renyi, 100000 nodes)", |b| {
+        b.iter(|| g.sssp_dijkstra_lazy(0))
+    });
+
+    let g = random_geometric::<f64>(n_nodes, 0.005, 42);
+
+    c.bench_function("Dijkstra lazy (random geometric, 100000 nodes)", |b| {
+        b.iter(|| g.sssp_dijkstra_lazy(0))
+    });
+}
+
+fn long_path(c: &mut Criterion) {
+    let n_hops = 50_000;
+    let g = SimpleGraph::<u32>::from_edges((0..n_hops).map(|ii| (ii, ii + 1, 1)));
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    c.bench_function("Path reconstruction (50000 hops)", |b| {
+        b.iter(|| lsp.get(n_hops))
+    });
+}
+
+fn reachability(c: &mut Criterion) {
+    let side = 300;
+    let g = grid(side);
+    let src = side * side / 2;
+    let dest = src + 2 * side + 2;
+
+    c.bench_function("Reachability of a nearby node (Dijkstra)", |b| {
+        b.iter(|| g.sssp_dijkstra(src, &[dest])[0].is_feasible())
+    });
+
+    c.bench_function("Reachability of a nearby node (BFS)", |b| {
+        b.iter(|| g.is_reachable(src, dest))
+    });
+}
+
+#[cfg(feature = "rayon")]
+fn parallel_dijkstra(c: &mut Criterion) {
+    let side = 100;
+    let g = grid(side);
+    let sources: Vec<usize> = (0..100).collect();
+
+    c.bench_function("Dijkstra lazy (100 sources)", |b| {
+        b.iter(|| {
+            sources
+                .iter()
+                .map(|&src| g.sssp_dijkstra_lazy(src))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    c.bench_function("Dijkstra lazy in parallel (100 sources)", |b| {
+        b.iter(|| g.sssp_dijkstra_many_par(&sources))
+    });
+
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    c.bench_function("Path reconstruction (all nodes)", |b| {
+        b.iter(|| lsp.get_all())
+    });
+
+    c.bench_function("Path reconstruction in parallel (all nodes)", |b| {
+        b.iter(|| lsp.get_all_par())
+    });
+}
+
+#[cfg(not(feature = "rayon"))]
+criterion_group!(
+    benches,
+    repeated_dijkstra,
+    random_graphs,
+    long_path,
+    reachability
+);
+#[cfg(feature = "rayon")]
+criterion_group!(
+    benches,
+    repeated_dijkstra,
+    random_graphs,
+    long_path,
+    reachability,
+    parallel_dijkstra
+);
+criterion_main!(benches);
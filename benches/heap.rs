@@ -1,10 +1,11 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use keyed_priority_queue::KeyedPriorityQueue;
-use pheap::PairingHeap;
+use pheap::{DeleteStrategy, IndexedPairingHeap, PairingHeap};
 use priority_queue::PriorityQueue;
 
 enum Queue {
     PairingHeap(PairingHeap<i32, i32>),
+    IndexedPairingHeap(IndexedPairingHeap<i32, i32>),
     AdPairingHeap(addressable_pairing_heap::PairingHeap<i32, i32>),
     A422PairingHeap(pairing_heap::PairingHeap<i32>),
     PriorityQueue(PriorityQueue<i32, i32>),
@@ -15,6 +16,9 @@ impl Queue {
     fn insert(&mut self, key: i32) {
         match self {
             Queue::PairingHeap(ph) => ph.insert(key, key),
+            Queue::IndexedPairingHeap(ph) => {
+                ph.insert(key, key);
+            }
             Queue::AdPairingHeap(ph) => {
                 ph.push(key, key);
             }
@@ -32,7 +36,14 @@ impl Queue {
 
     fn update(&mut self, key: i32, delta: i32) {
         match self {
-            Queue::PairingHeap(ph) => ph.decrease_prio(&key, delta),
+            Queue::PairingHeap(ph) => {
+                ph.decrease_prio(&key, delta);
+            }
+            Queue::IndexedPairingHeap(ph) => {
+                if let Some(q) = ph.get_prio(&key).cloned() {
+                    ph.decrease_prio_indexed(&key, q - delta);
+                }
+            }
             Queue::AdPairingHeap(_) => unimplemented!(),
             Queue::PriorityQueue(pq) => {
                 if let Some(q) = pq.get_priority(&key).cloned() {
@@ -53,6 +64,9 @@ impl Queue {
             Queue::PairingHeap(ph) => {
                 ph.delete_min();
             }
+            Queue::IndexedPairingHeap(ph) => {
+                ph.delete_min();
+            }
             Queue::AdPairingHeap(ph) => {
                 ph.pop();
             }
@@ -71,6 +85,8 @@ impl Queue {
 
 enum QueueKind {
     PairingHeap,
+    PairingHeapMultipass,
+    IndexedPairingHeap,
     AdPairingHeap,
     A422PairingHeap,
     PriorityQueue,
@@ -81,6 +97,12 @@ impl QueueKind {
     fn create(&self) -> Queue {
         match self {
             QueueKind::PairingHeap => Queue::PairingHeap(PairingHeap::<i32, i32>::new()),
+            QueueKind::PairingHeapMultipass => Queue::PairingHeap(
+                PairingHeap::<i32, i32>::with_delete_strategy(DeleteStrategy::Multipass),
+            ),
+            QueueKind::IndexedPairingHeap => {
+                Queue::IndexedPairingHeap(IndexedPairingHeap::<i32, i32>::new_indexed())
+            }
             QueueKind::AdPairingHeap => {
                 Queue::AdPairingHeap(addressable_pairing_heap::PairingHeap::<i32, i32>::new())
             }
@@ -129,6 +151,12 @@ fn no_change_prio(c: &mut Criterion) {
     c.bench_function("Pairing Heap (1000)", |b| {
         b.iter(|| QueueKind::PairingHeap.bench(1_000, false))
     });
+    c.bench_function("Pairing Heap Multipass (1000)", |b| {
+        b.iter(|| QueueKind::PairingHeapMultipass.bench(1_000, false))
+    });
+    c.bench_function("Indexed Pairing Heap (1000)", |b| {
+        b.iter(|| QueueKind::IndexedPairingHeap.bench(1_000, false))
+    });
     c.bench_function("Addressable Pairing Heap (1000)", |b| {
         b.iter(|| QueueKind::AdPairingHeap.bench(1_000, false))
     });
@@ -147,6 +175,12 @@ fn with_change_prio(c: &mut Criterion) {
     c.bench_function("Pairing Heap (1000) | Priority Update", |b| {
         b.iter(|| QueueKind::PairingHeap.bench(1_000, true))
     });
+    c.bench_function("Pairing Heap Multipass (1000) | Priority Update", |b| {
+        b.iter(|| QueueKind::PairingHeapMultipass.bench(1_000, true))
+    });
+    c.bench_function("Indexed Pairing Heap (1000) | Priority Update", |b| {
+        b.iter(|| QueueKind::IndexedPairingHeap.bench(1_000, true))
+    });
     c.bench_function("Priority Queue (1000) | Priority Update", |b| {
         b.iter(|| QueueKind::PriorityQueue.bench(1_000, true))
     });
@@ -6,7 +6,9 @@ use std::{
 
 use clap::{App, Arg};
 use pathfinding::prelude::dijkstra_all;
-use pheap::graph::SimpleGraph;
+use pheap::graph::{
+    alt::AltPreprocessed, generate::erdos_renyi, DijkstraStrategy, DijkstraVisitor, SimpleGraph,
+};
 
 fn main() {
     let matches = App::new("Single source shortest path benchmark")
@@ -15,9 +17,16 @@ fn main() {
                 .short("f")
                 .long("file")
                 .takes_value(true)
-                .required(true)
+                .required_unless("random")
                 .help("Path to a DIMACS file."),
         )
+        .arg(
+            Arg::with_name("random")
+                .long("random")
+                .takes_value(true)
+                .conflicts_with("file")
+                .help("Generates an Erdos-Renyi graph with the given number of nodes and a mean degree of 4 instead of loading a file. Only supported by pheap."),
+        )
         .arg(
             Arg::with_name("lib")
                 .long("lib")
@@ -32,12 +41,37 @@ fn main() {
                 .default_value("5")
                 .help("Number of runs for search query."),
         )
+        .arg(
+            Arg::with_name("cutoff")
+                .long("cutoff")
+                .takes_value(true)
+                .help("Maximum distance from the source node. Only supported by pheap."),
+        )
+        .arg(
+            Arg::with_name("strategy")
+                .long("strategy")
+                .takes_value(true)
+                .default_value("decrease_key")
+                .help("How pheap updates the priority queue. Options: decrease_key | reinsert."),
+        )
+        .arg(
+            Arg::with_name("landmarks")
+                .long("landmarks")
+                .takes_value(true)
+                .help("Compares point-to-point queries with ALT using the given number of landmarks against Dijkstra. Only supported by pheap."),
+        )
+        .arg(
+            Arg::with_name("paths")
+                .long("paths")
+                .help("Also measures the time to build the paths to all nodes. Only supported by pheap."),
+        )
         .get_matches();
 
-    let filepath = match matches.value_of("file") {
-        Some(fp) => fp,
-        None => std::process::exit(1),
-    };
+    let filepath = matches.value_of("file");
+
+    let random = matches
+        .value_of("random")
+        .map(|n| n.parse::<usize>().unwrap());
 
     let runs = matches
         .value_of("runs")
@@ -46,10 +80,45 @@ fn main() {
         .parse::<usize>()
         .unwrap();
 
+    let cutoff = matches
+        .value_of("cutoff")
+        .map(|c| c.parse::<u32>().unwrap());
+
+    let landmarks = matches
+        .value_of("landmarks")
+        .map(|l| l.parse::<usize>().unwrap());
+
+    let strategy = match matches.value_of("strategy").unwrap() {
+        "decrease_key" => DijkstraStrategy::DecreaseKey,
+        "reinsert" => DijkstraStrategy::Reinsert,
+        _ => std::process::exit(1),
+    };
+
     match matches.value_of("lib") {
         Some(lib) => match lib {
-            "pheap" => graph(filepath, runs),
-            "pathfinding" => pathfinding(filepath, runs),
+            "pheap" => {
+                let g = match (filepath, random) {
+                    (_, Some(n_nodes)) => {
+                        println!("> Generate Erdos-Renyi graph with {} nodes", n_nodes);
+                        erdos_renyi(n_nodes, 4.0 / n_nodes as f64, 1..100, 42)
+                    }
+                    (Some(fp), None) => load_graph(fp),
+                    (None, None) => std::process::exit(1),
+                };
+
+                graph(
+                    g,
+                    runs,
+                    cutoff,
+                    strategy,
+                    landmarks,
+                    matches.is_present("paths"),
+                )
+            }
+            "pathfinding" => match filepath {
+                Some(fp) => pathfinding(fp, runs),
+                None => std::process::exit(1),
+            },
             _ => std::process::exit(1),
         },
         None => std::process::exit(1),
@@ -77,7 +146,7 @@ macro_rules! run_exp {
     };
 }
 
-fn graph(filepath: &str, runs: usize) {
+fn load_graph(filepath: &str) -> SimpleGraph<u32> {
     println!("> Load file: {}", filepath);
 
     let file = File::open(filepath).unwrap();
@@ -98,15 +167,131 @@ fn graph(filepath: &str, runs: usize) {
     }
 
     let mut g = SimpleGraph::<u32>::with_capacity(n_nodes);
+    g.extend(reader.lines().map(|line| parse_line(&line.unwrap())));
+    g
+}
 
-    for line in reader.lines() {
-        let (node1, node2, weight) = parse_line(&line.unwrap());
-        g.add_weighted_edges(node1, node2, weight);
-    }
+fn graph(
+    mut g: SimpleGraph<u32>,
+    runs: usize,
+    cutoff: Option<u32>,
+    strategy: DijkstraStrategy,
+    landmarks: Option<usize>,
+    paths: bool,
+) {
+    // Sorted adjacency lists are more cache friendly, and the spare capacity left over from
+    // loading is no longer needed.
+    g.finalize();
+    g.shrink_to_fit();
 
     println!("> Graph created.");
+    println!(
+        "> Nodes: {} | Max degree: {} | Mean degree: {:.2}",
+        g.n_nodes(),
+        g.max_degree(),
+        g.mean_degree()
+    );
 
-    run_exp!(runs, let _ = g.sssp_dijkstra_lazy(10_000));
+    for (degree, count) in g.degree_histogram() {
+        println!(">   Degree {}: {} nodes", degree, count);
+    }
+
+    // Small generated graphs may have less than 10001 nodes.
+    let src = 10_000.min(g.n_nodes() - 1);
+
+    if let Some(n_landmarks) = landmarks {
+        let start = std::time::Instant::now();
+        let alt = AltPreprocessed::build(&g, n_landmarks);
+        println!(
+            "> Landmarks {:?} selected in {} (ms)",
+            alt.landmarks(),
+            start.elapsed().as_millis()
+        );
+
+        let dest = g.n_nodes() - 1;
+        assert_eq!(
+            g.sssp_dijkstra(src, &[dest])[0].dist(),
+            alt.query(&g, src, dest).dist(),
+            "ALT and Dijkstra disagree on the distance from {} to {}",
+            src,
+            dest
+        );
+
+        println!("> Dijkstra from {} to {}", src, dest);
+        run_exp!(runs, let _ = g.sssp_dijkstra(src, &[dest]));
+        println!("> ALT from {} to {}", src, dest);
+        run_exp!(runs, let _ = alt.query(&g, src, dest));
+        return;
+    }
+
+    match cutoff {
+        Some(max_dist) => {
+            println!("> Cutoff: {}", max_dist);
+            run_exp!(runs, let _ = g.sssp_dijkstra_within(src, max_dist));
+        }
+        None => {
+            println!("> Strategy: {:?}", strategy);
+            let mut queue = QueueSize::new(strategy, g.n_nodes(), src);
+            let _ = g.sssp_dijkstra_visit_with_strategy(src, strategy, &mut queue);
+            println!("> Peak size of the priority queue: {}", queue.peak);
+            run_exp!(runs, let _ = g.sssp_dijkstra_lazy_with_strategy(src, strategy));
+        }
+    }
+
+    if paths {
+        let lsp = g.sssp_dijkstra_lazy(src);
+        println!("> Build all paths");
+        run_exp!(runs, let _ = lsp.get_all());
+
+        #[cfg(feature = "rayon")]
+        {
+            println!("> Build all paths in parallel");
+            run_exp!(runs, let _ = lsp.get_all_par());
+        }
+    }
+}
+
+/// Tracks the number of entries in the priority queue of Dijkstra's algorithm.
+struct QueueSize {
+    strategy: DijkstraStrategy,
+    queued: Vec<bool>,
+    len: usize,
+    peak: usize,
+}
+
+impl QueueSize {
+    /// The queue starts with the entry of the source node.
+    fn new(strategy: DijkstraStrategy, n_nodes: usize, src: usize) -> Self {
+        let mut queued = vec![false; n_nodes];
+        queued[src] = true;
+
+        Self {
+            strategy,
+            queued,
+            len: 1,
+            peak: 1,
+        }
+    }
+}
+
+impl DijkstraVisitor<u32> for QueueSize {
+    fn on_settle(&mut self, node: usize, _dist: u32) {
+        self.queued[node] = false;
+        self.len -= 1;
+    }
+
+    fn on_relax(&mut self, _from: usize, to: usize, _new_dist: u32) {
+        // Decreasing the key of a queued node doesn't add an entry, reinserting always does.
+        if self.strategy == DijkstraStrategy::Reinsert || !self.queued[to] {
+            self.queued[to] = true;
+            self.len += 1;
+            self.peak = self.peak.max(self.len);
+        }
+    }
+
+    fn on_skip(&mut self, _node: usize) {
+        self.len -= 1;
+    }
 }
 
 fn pathfinding(filepath: &str, runs: usize) {
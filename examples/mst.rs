@@ -97,11 +97,7 @@ fn graph(filepath: &str, runs: usize) {
     }
 
     let mut g = SimpleGraph::<u32>::with_capacity(n_nodes);
-
-    for line in reader.lines() {
-        let (node1, node2, weight) = parse_line(&line.unwrap());
-        g.add_weighted_edges(node1, node2, weight);
-    }
+    g.extend(reader.lines().map(|line| parse_line(&line.unwrap())));
 
     println!("> Graph created.");
 
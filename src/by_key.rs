@@ -0,0 +1,116 @@
+use std::{fmt, marker::PhantomData};
+
+use crate::{ph::Order, PairingHeap};
+
+/// A min-pairing heap whose elements are ordered by a priority derived from the elements themselves.
+///
+/// Instead of storing a priority next to each element, the heap computes it with an extractor function,
+/// so that the element stays the single source of truth for its priority. The extractor is stored once
+/// in the heap and called whenever two elements are compared, thus it may be any function or closure,
+/// including a boxed one chosen at runtime.
+///
+/// # Examples
+/// ```
+/// use pheap::PairingHeapByKey;
+///
+/// struct Task {
+///     name: &'static str,
+///     deadline: u32,
+/// }
+///
+/// let mut heap = PairingHeapByKey::new_by_key(|t: &Task| &t.deadline);
+/// heap.insert(Task { name: "b", deadline: 20 });
+/// heap.insert(Task { name: "a", deadline: 10 });
+///
+/// assert_eq!("a", heap.find_min().unwrap().name);
+/// assert_eq!(10, heap.delete_min().unwrap().deadline);
+/// assert_eq!(1, heap.len());
+/// ```
+pub struct PairingHeapByKey<K, P, F> {
+    heap: PairingHeap<K, ()>,
+    order: ByKey<F, P>,
+}
+
+impl<K, P, F> PairingHeapByKey<K, P, F>
+where
+    F: Fn(&K) -> &P,
+    P: PartialOrd,
+{
+    /// Creates an empty heap, which orders its elements by the priority returned from ```f```.
+    pub fn new_by_key(f: F) -> Self {
+        Self {
+            heap: PairingHeap::new(),
+            order: ByKey {
+                f,
+                marker: PhantomData,
+            },
+        }
+    }
+
+    /// Returns the number of elements stored in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks whether the heap is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the element with the minimum priority.
+    #[inline]
+    pub fn find_min(&self) -> Option<&K> {
+        self.heap.find_min().map(|(elmt, _)| elmt)
+    }
+
+    /// Inserts a new element to the heap.
+    #[inline]
+    pub fn insert(&mut self, elmt: K) {
+        self.heap.insert_by(elmt, (), (), &self.order);
+    }
+
+    /// Deletes the element with the minimum priority and returns it.
+    #[inline]
+    pub fn delete_min(&mut self) -> Option<K> {
+        self.heap
+            .delete_min_by(&self.order)
+            .map(|(elmt, _, _)| elmt)
+    }
+
+    /// Merges two heaps together and forms a new heap.
+    ///
+    /// The extractor of ```self``` is kept for the new heap.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            heap: self.heap.merge_by(other.heap, &self.order),
+            order: self.order,
+        }
+    }
+}
+
+impl<K, P, F> fmt::Debug for PairingHeapByKey<K, P, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PairingHeapByKey")
+            .field("len", &self.heap.len())
+            .finish()
+    }
+}
+
+/// Orders the elements by the priority their extractor returns.
+struct ByKey<F, P> {
+    f: F,
+    marker: PhantomData<fn() -> P>,
+}
+
+impl<K, P, F> Order<K, ()> for ByKey<F, P>
+where
+    F: Fn(&K) -> &P,
+    P: PartialOrd,
+{
+    #[inline(always)]
+    fn precedes(&self, elmt1: &K, _: &(), elmt2: &K, _: &()) -> bool {
+        (self.f)(elmt1) < (self.f)(elmt2)
+    }
+}
@@ -1,21 +1,85 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     fs::File,
-    io::{LineWriter, Write},
+    io::{BufRead, BufReader, ErrorKind, LineWriter, Write},
+    iter::FromIterator,
     path::Path,
+    str::FromStr,
 };
 
-use std::ops::AddAssign;
+use std::ops::{AddAssign, Index, Sub};
 
-use num_traits::{Bounded, Num, Zero};
+use num_traits::{Bounded, Num, One, ToPrimitive, Zero};
 
-use crate::{ph::HeapElmt, PairingHeap};
+use crate::{
+    ph::HeapElmt,
+    stable::{StablePairingHeap, Stamped},
+    PairingHeap,
+};
+
+pub mod alt;
+pub mod generate;
+
+mod labeled;
+pub use labeled::LabeledGraph;
+
+/// The distance of a node that is not reached by a shortest path algorithm.
+///
+/// For floating point weights, this is infinity, so that no finite distance compares equal to it. For all
+/// other types, it defaults to ```max_value()```, thus a custom weight type only needs an empty
+/// implementation.
+pub trait Infinity: Bounded + Sized {
+    /// Returns the distance of a node that is not reached yet.
+    fn infinity() -> Self {
+        Self::max_value()
+    }
+}
+
+impl Infinity for f32 {
+    fn infinity() -> Self {
+        f32::INFINITY
+    }
+}
+
+impl Infinity for f64 {
+    fn infinity() -> Self {
+        f64::INFINITY
+    }
+}
+
+impl Infinity for u8 {}
+impl Infinity for u16 {}
+impl Infinity for u32 {}
+impl Infinity for u64 {}
+impl Infinity for u128 {}
+impl Infinity for usize {}
+impl Infinity for i8 {}
+impl Infinity for i16 {}
+impl Infinity for i32 {}
+impl Infinity for i64 {}
+impl Infinity for i128 {}
+impl Infinity for isize {}
 
 /// A simple and undirected graph.
 ///
 /// A simple graph assumes that the node indexing starts from ```0``` and is not equipped with a hash map
 /// for a mapping from external complex objects to internal graph indices. As a result, [`SimpleGraph`]
-/// doesn't have no runtime overhead for such object storage and mapping.
+/// doesn't have no runtime overhead for such object storage and mapping. [`LabeledGraph`] adds such a
+/// mapping on top of it.
+///
+/// Besides its weight, every edge can carry a payload of type ```E```, e.g. a street name, which defaults to
+/// ```()```. See [`SimpleGraph::with_edge_data`]. All other constructors, such as [`SimpleGraph::new`],
+/// [`SimpleGraph::from_edges`], [`SimpleGraph::from_grid`] and [`SimpleGraph::read_edgelist`], create graphs
+/// without payloads. Transformations like [`SimpleGraph::reverse`] and [`mst_prim`] keep the payloads.
+///
+/// Edges added with [`SimpleGraph::add_weighted_edges`] are undirected. For algorithms on directed graphs,
+/// such as [`SimpleGraph::dag_shortest_path`], directed arcs can be added with [`SimpleGraph::add_weighted_arc`].
+///
+/// Floating point weights are supported. ```NaN``` weights are rejected when an edge is added. For
+/// Dijkstra's algorithm and [`SimpleGraph::dag_shortest_path`], an infinite weight behaves like a missing
+/// edge: a node whose distance would be infinite, also after an overflow of a sum of finite weights, is
+/// unreachable. Prim's algorithm, e.g. in [`mst_prim`], ignores edges with a weight of at least
+/// ```W::max_value()```, such as ```f64::MAX``` and infinity.
 ///
 /// # Examples
 /// The following example shows how to construct a graph and find the shortest path between node 1 and 5.
@@ -43,7 +107,7 @@ use crate::{ph::HeapElmt, PairingHeap};
 ///
 /// let sp = sp.pop().unwrap();
 /// assert_eq!(20, sp.dist());
-/// assert_eq!(&[0, 2, 5, 4], sp.path().as_slice());
+/// assert_eq!(&[0, 2, 5, 4], sp.path());
 ///
 /// // Adds a disconnected component to the graph.
 /// g.add_weighted_edges(6, 7, 2);
@@ -58,14 +122,24 @@ use crate::{ph::HeapElmt, PairingHeap};
 /// let sp = lsp.get(4);
 /// assert_eq!(true, sp.is_feasible());
 /// assert_eq!(20, sp.dist());
-/// assert_eq!(&[0, 2, 5, 4], sp.path().as_slice());
+/// assert_eq!(&[0, 2, 5, 4], sp.path());
+///
+/// // Lists the weight of each hop along the path.
+/// assert_eq!(3, sp.hops());
+/// assert_eq!(vec![(0, 2, 9), (2, 5, 2), (5, 4, 9)], sp.segments());
 ///
 /// ```
 ///
 #[derive(Debug, Default)]
-pub struct SimpleGraph<W> {
+pub struct SimpleGraph<W, E = ()> {
     n_edges: usize,
     weights: HashMap<usize, Vec<(usize, W)>>,
+    /// Flag indicating whether all adjacency lists are sorted by neighbour index.
+    sorted: bool,
+    /// Initial capacity of the adjacency list of a newly added node.
+    degree_hint: usize,
+    /// Payloads of the edges, keyed by their end nodes in ascending order.
+    edge_data: HashMap<(usize, usize), E>,
 }
 
 impl<W> SimpleGraph<W> {
@@ -74,6 +148,9 @@ impl<W> SimpleGraph<W> {
         Self {
             n_edges: 0,
             weights: HashMap::new(),
+            sorted: false,
+            degree_hint: 0,
+            edge_data: HashMap::new(),
         }
     }
 
@@ -82,7 +159,440 @@ impl<W> SimpleGraph<W> {
         Self {
             n_edges: 0,
             weights: HashMap::with_capacity(n_nodes),
+            sorted: false,
+            degree_hint: 0,
+            edge_data: HashMap::new(),
+        }
+    }
+
+    /// Creates an empty graph with the given capacity of nodes, whose adjacency lists are pre-sized to hold
+    /// ```avg_degree``` edges each.
+    ///
+    /// This avoids the repeated reallocation of adjacency lists while a dense graph is loaded. Lists of nodes
+    /// with more neighbours still grow as needed.
+    pub fn with_degree_hint(n_nodes: usize, avg_degree: usize) -> Self {
+        Self {
+            n_edges: 0,
+            weights: HashMap::with_capacity(n_nodes),
+            sorted: false,
+            degree_hint: avg_degree,
+            edge_data: HashMap::new(),
+        }
+    }
+
+    /// Creates a graph from its number of edges and its adjacency lists, as returned by
+    /// [`SimpleGraph::into_parts`].
+    ///
+    /// The map assigns each node the list of its neighbours and the weights of the connecting edges. An
+    /// undirected edge must be stored in the lists of both of its end nodes. Neighbours without a list of
+    /// their own, such as the heads of directed arcs, are added as nodes, like in
+    /// [`SimpleGraph::add_weighted_arc`].
+    ///
+    /// # Panics
+    /// Panics if any weight is ```NaN```, like [`SimpleGraph::add_weighted_edges`].
+    pub fn from_parts(n_edges: usize, mut weights: HashMap<usize, Vec<(usize, W)>>) -> Self
+    where
+        W: PartialOrd,
+    {
+        let mut missing = Vec::new();
+        for (node, nb) in &weights {
+            for (idx, w) in nb {
+                assert_comparable(*node, *idx, w);
+                if !weights.contains_key(idx) {
+                    missing.push(*idx);
+                }
+            }
+        }
+
+        for node in missing {
+            weights.entry(node).or_default();
+        }
+
+        Self {
+            n_edges,
+            weights,
+            sorted: false,
+            degree_hint: 0,
+            edge_data: HashMap::new(),
+        }
+    }
+
+    /// Creates a graph from an iterator of weighted edges ```(node 1, node 2, weight)```.
+    ///
+    /// Each edge is added with [`SimpleGraph::add_weighted_edges`], so a repeated edge updates the weight of
+    /// the edge added before, see [`SimpleGraph::from_edges_dedup`] to keep the minimum weight instead. The
+    /// size hint of the iterator is used to pre-allocate the storage of the graph.
+    pub fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+        W: Copy + PartialOrd,
+    {
+        let mut graph = Self::new();
+        graph.extend(edges);
+        graph
+    }
+
+    /// Creates a graph from an iterator of weighted edges ```(node 1, node 2, weight)``` and collapses
+    /// repeated edges into one edge with the minimum weight, like [`SimpleGraph::dedup_edges`].
+    pub fn from_edges_dedup<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+        W: Copy + PartialOrd,
+    {
+        let edges = edges.into_iter();
+        let mut graph = Self::with_capacity(edges.size_hint().0);
+
+        for (node1, node2, weight) in edges {
+            graph.add_or_resolve_edge(node1, node2, weight, |w1, w2| if w2 < w1 { w2 } else { w1 });
+        }
+
+        graph
+    }
+
+    /// Creates a graph from a 2D matrix of cell costs, e.g. a game map or a raster.
+    ///
+    /// Each cell becomes a node, whose index is given by [`grid_index`] with the number of columns of the
+    /// first row as width. A cell is connected to its 4 horizontal and vertical neighbours, or to all 8
+    /// neighbours if ```diagonal``` is ```true```. Moving into a cell costs the cost of that cell, so the
+    /// graph consists of directed arcs weighted by the cost of their destination cell. A diagonal move
+    /// costs the same as any other move and is only possible if both cells it cuts across are passable.
+    ///
+    /// Cells with the cost ```None``` are impassable: they are added as nodes without any edges.
+    ///
+    /// # Panics
+    /// Panics if the rows don't all have the same length.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::graph::{grid_index, SimpleGraph};
+    ///
+    /// // A wall in the middle column with a gap at the bottom.
+    /// let costs = vec![
+    ///     vec![Some(1), None, Some(1)],
+    ///     vec![Some(1), None, Some(1)],
+    ///     vec![Some(1), Some(1), Some(1)],
+    /// ];
+    /// let g = SimpleGraph::<u32>::from_grid(&costs, false);
+    ///
+    /// let (src, dest) = (grid_index(0, 0, 3), grid_index(0, 2, 3));
+    /// let sp = &g.sssp_dijkstra(src, &[dest])[0];
+    /// assert_eq!(6, sp.dist());
+    /// assert_eq!(&[0, 3, 6, 7, 8, 5, 2], sp.path());
+    /// ```
+    pub fn from_grid(costs: &[Vec<Option<W>>], diagonal: bool) -> Self
+    where
+        W: Copy + PartialOrd,
+    {
+        let width = costs.first().map_or(0, |row| row.len());
+        assert!(
+            costs.iter().all(|row| row.len() == width),
+            "all rows of the grid must have the same length"
+        );
+
+        let degree = if diagonal { 8 } else { 4 };
+        let mut graph = Self::with_degree_hint(costs.len() * width, degree);
+        let cost = |row: isize, col: isize| {
+            if row < 0 || col < 0 || col >= width as isize {
+                return None;
+            }
+
+            costs.get(row as usize).and_then(|r| r[col as usize])
+        };
+
+        for (row, cells) in costs.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                let node = grid_index(row, col, width);
+                graph.add_node(node);
+
+                if cell.is_none() {
+                    continue;
+                }
+
+                let (r, c) = (row as isize, col as isize);
+                for (dr, dc) in [
+                    (-1, -1),
+                    (-1, 0),
+                    (-1, 1),
+                    (0, -1),
+                    (0, 1),
+                    (1, -1),
+                    (1, 0),
+                    (1, 1),
+                ] {
+                    let is_diagonal = dr != 0 && dc != 0;
+                    if is_diagonal
+                        && (!diagonal || cost(r + dr, c).is_none() || cost(r, c + dc).is_none())
+                    {
+                        continue;
+                    }
+
+                    if let Some(weight) = cost(r + dr, c + dc) {
+                        let next = grid_index((r + dr) as usize, (c + dc) as usize, width);
+                        graph.add_weighted_arc(node, next, weight);
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Reads a graph from a list of edges written by [`SimpleGraph::write_edgelist`].
+    ///
+    /// Each line must contain one edge in [networkx](https://networkx.org/)'s format:
+    /// ```index 1 index 2 {'weight': {}}```. Blank lines and comments starting with ```#``` are skipped.
+    ///
+    /// Since [`SimpleGraph::write_edgelist`] writes every edge once per direction, each line is added
+    /// as a single adjacency entry, so that a write/read round-trip reproduces the original graph.
+    ///
+    /// Like any other malformed line, a ```NaN``` weight is rejected with [`ErrorKind::InvalidData`]. The
+    /// format has no room for payloads, so the graph is read without them.
+    pub fn read_edgelist<P>(filepath: P) -> std::io::Result<Self>
+    where
+        P: AsRef<Path>,
+        W: FromStr + PartialOrd,
+    {
+        let file = File::open(filepath)?;
+        let reader = BufReader::new(file);
+        let mut graph = Self::new();
+
+        for (line_no, line) in reader.lines().enumerate() {
+            let line = line?;
+            let content = line.split('#').next().unwrap_or("").trim();
+
+            if content.is_empty() {
+                continue;
+            }
+
+            let (node1, node2, weight) = parse_edgelist_line::<W>(content)
+                .filter(|(_, _, weight)| weight.partial_cmp(weight).is_some())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("invalid edge at line {}: {}", line_no + 1, line),
+                    )
+                })?;
+
+            graph.insert_weight(node1, node2, weight);
+            graph.n_edges += 1;
+        }
+
+        Ok(graph)
+    }
+}
+
+impl<W, E> SimpleGraph<W, E> {
+    /// Creates an empty graph whose edges can carry a payload of type ```E```, e.g. a street name, besides
+    /// their weights.
+    ///
+    /// Payloads are added with [`SimpleGraph::add_weighted_edge_with`] and stored apart from the adjacency
+    /// lists, so that the graph algorithms never touch them. A graph without payloads, i.e. ```E = ()```,
+    /// is created with [`SimpleGraph::new`] and doesn't allocate any storage for them.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::graph::SimpleGraph;
+    ///
+    /// let mut g = SimpleGraph::with_edge_data();
+    /// g.add_weighted_edge_with(0, 1, 300, "Main Street");
+    /// g.add_weighted_edge_with(1, 2, 120, "Bridge Road");
+    /// g.add_weighted_edge_with(0, 2, 600, "Ring Road");
+    ///
+    /// let sp = &g.sssp_dijkstra(0, &[2])[0];
+    /// assert_eq!(420, sp.dist());
+    ///
+    /// for (step, street) in sp.edge_data(&g).into_iter().enumerate() {
+    ///     println!("{}. Follow {}", step + 1, street.unwrap());
+    /// }
+    /// assert_eq!(vec![Some(&"Main Street"), Some(&"Bridge Road")], sp.edge_data(&g));
+    /// ```
+    pub fn with_edge_data() -> Self {
+        Self {
+            n_edges: 0,
+            weights: HashMap::new(),
+            sorted: false,
+            degree_hint: 0,
+            edge_data: HashMap::new(),
+        }
+    }
+
+    /// Adds all nodes and edges of another graph to this graph.
+    ///
+    /// If an edge exists in both graphs, the smaller weight is kept. Payloads of the other graph are copied
+    /// for edges that don't have a payload in this graph yet.
+    pub fn merge_from(&mut self, other: &SimpleGraph<W, E>)
+    where
+        W: Copy + PartialOrd,
+        E: Clone,
+    {
+        self.merge_from_with(other, |w1, w2| if w2 < w1 { w2 } else { w1 });
+    }
+
+    /// Adds all nodes and edges of another graph to this graph.
+    ///
+    /// If an edge exists in both graphs, its weight is replaced by ```resolve(weight in self, weight in other)```
+    /// and its payload in this graph is kept, see [`SimpleGraph::merge_from`].
+    pub fn merge_from_with<F>(&mut self, other: &SimpleGraph<W, E>, resolve: F)
+    where
+        W: Copy + PartialOrd,
+        E: Clone,
+        F: FnMut(W, W) -> W,
+    {
+        self.merge_weights(other, resolve);
+
+        for (key, data) in &other.edge_data {
+            self.edge_data.entry(*key).or_insert_with(|| data.clone());
+        }
+    }
+
+    /// Merges two graphs together and returns the union of both.
+    ///
+    /// If an edge exists in both graphs, the smaller weight and the payload of ```self``` are kept.
+    pub fn merge(mut self, other: SimpleGraph<W, E>) -> Self
+    where
+        W: Copy + PartialOrd,
+    {
+        self.merge_weights(&other, |w1, w2| if w2 < w1 { w2 } else { w1 });
+
+        for (key, data) in other.edge_data {
+            self.edge_data.entry(key).or_insert(data);
+        }
+
+        self
+    }
+
+    /// Returns an undirected copy of the graph, in which every directed arc is complemented by an arc
+    /// in the opposite direction with the same weight.
+    ///
+    /// Arcs whose reverse already exists, such as the two halves of an edge added with
+    /// [`SimpleGraph::add_weighted_edges`], are copied as they are. Since a payload belongs to both
+    /// directions of an edge, all payloads are copied as well.
+    pub fn to_undirected(&self) -> Self
+    where
+        W: Copy + PartialOrd,
+        E: Clone,
+    {
+        let mut graph = self.empty_copy();
+
+        for (node, nb) in &self.weights {
+            graph.add_node(*node);
+
+            for (idx, w) in nb {
+                graph.add_weighted_arc(*node, *idx, *w);
+                if !self.has_edge(*idx, *node) {
+                    graph.add_weighted_arc(*idx, *node, *w);
+                }
+            }
+        }
+
+        if self.sorted {
+            graph.finalize();
+        }
+
+        graph
+    }
+
+    /// Returns the reverse (or transpose) of the graph, in which every directed arc ```u -> v``` becomes
+    /// an arc ```v -> u``` with the same weight and payload.
+    ///
+    /// Searching the reverse graph from a node finds the shortest paths *to* that node in the original
+    /// graph, and the degree of a node in the reverse graph is its in-degree in the original one. An
+    /// undirected graph, in which every edge is stored in both directions, is its own reverse, so this
+    /// is just a copy of it.
+    pub fn reverse(&self) -> Self
+    where
+        W: Copy + PartialOrd,
+        E: Clone,
+    {
+        let mut graph = self.empty_copy();
+
+        for (node, nb) in &self.weights {
+            graph.add_node(*node);
+
+            for (idx, w) in nb {
+                graph.add_weighted_arc(*idx, *node, *w);
+            }
+        }
+
+        if self.sorted {
+            graph.finalize();
+        }
+
+        graph
+    }
+
+    /// Returns a graph without edges, which has the node capacity and the payloads of this graph.
+    fn empty_copy(&self) -> Self
+    where
+        E: Clone,
+    {
+        Self {
+            n_edges: 0,
+            weights: HashMap::with_capacity(self.weights.len()),
+            sorted: false,
+            degree_hint: 0,
+            edge_data: self.edge_data.clone(),
+        }
+    }
+
+    /// Adds all nodes and the weights of all edges of another graph to this graph, without their payloads.
+    fn merge_weights<F>(&mut self, other: &SimpleGraph<W, E>, mut resolve: F)
+    where
+        W: Copy + PartialOrd,
+        F: FnMut(W, W) -> W,
+    {
+        self.reserve_nodes(other.weights.len());
+
+        for (node, nb) in &other.weights {
+            self.add_node(*node);
+
+            for (idx, w) in nb {
+                if self.upsert_weight(*node, *idx, *w, &mut resolve) {
+                    self.n_edges += 1;
+                }
+            }
+        }
+    }
+
+    /// Decomposes the graph into its number of edges and its adjacency lists.
+    ///
+    /// This allows to reuse the allocated storage for another graph, e.g. by clearing the map and
+    /// passing it to [`SimpleGraph::from_parts`] again.
+    pub fn into_parts(self) -> (usize, HashMap<usize, Vec<(usize, W)>>) {
+        (self.n_edges, self.weights)
+    }
+
+    /// Reserves capacity for at least ```additional``` more nodes.
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        self.weights.reserve(additional);
+    }
+
+    /// Reserves capacity for at least ```additional``` more edges in the adjacency list of a node.
+    ///
+    /// If the node doesn't exist in the graph, it will be added.
+    pub fn reserve_edges_for(&mut self, node: usize, additional: usize) {
+        self.adjacency_mut(node).reserve(additional);
+    }
+
+    /// Shrinks the capacity of the node storage and of every adjacency list as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.weights.shrink_to_fit();
+        for nb in self.weights.values_mut() {
+            nb.shrink_to_fit();
+        }
+    }
+
+    /// Sorts every adjacency list by neighbour index.
+    ///
+    /// This improves the cache behaviour of algorithms traversing the graph, and lets
+    /// [`SimpleGraph::edge_weight`] and [`SimpleGraph::has_edge`] use a binary search instead of a linear
+    /// scan. Adding edges afterwards reverts the lookups to a linear scan until the next call.
+    pub fn finalize(&mut self) {
+        for nb in self.weights.values_mut() {
+            nb.sort_by_key(|(idx, _)| *idx);
         }
+
+        self.sorted = true;
     }
 
     /// Returns the number of nodes in the graph.
@@ -90,150 +600,2219 @@ impl<W> SimpleGraph<W> {
         self.weights.len()
     }
 
+    /// Returns the number of slots required to index all nodes by position, i.e. the highest node
+    /// index plus one.
+    #[inline]
+    pub(crate) fn node_bound(&self) -> usize {
+        self.weights.keys().max().map_or(0, |max| max + 1)
+    }
+
     /// Returns the number of edges in the graph.
     pub fn n_edges(&self) -> usize {
         self.n_edges
     }
 
-    /// Adds a weighted edge to the graph.
+    /// Returns the sum of the weights of all edges in the graph.
     ///
-    /// If the edge already exists in the graph, the weight will be updated.
-    pub fn add_weighted_edges(&mut self, node1: usize, node2: usize, weight: W)
+    /// An undirected edge is stored once per direction, but its weight is counted only once. Parallel
+    /// edges are counted individually. A directed arc whose reverse also exists is treated as one half
+    /// of an undirected edge.
+    pub fn total_weight(&self) -> W
     where
-        W: Clone + Copy,
+        W: Zero + AddAssign + Copy,
     {
-        if node1 != node2 {
-            self.insert_weight(node1, node2, weight);
-            self.insert_weight(node2, node1, weight);
+        let mut total = W::zero();
+
+        for (node, nb) in &self.weights {
+            for (idx, w) in nb {
+                if idx >= node || !self.has_edge(*idx, *node) {
+                    total += *w;
+                }
+            }
         }
 
-        self.n_edges += 2;
+        total
     }
 
-    /// Returns the neighbours of a node.
-    #[inline]
-    pub(crate) fn neighbours(&self, node: &usize) -> Option<&Vec<(usize, W)>> {
-        self.weights.get(&node)
+    /// Returns the degree of a node, i.e. the number of entries in its adjacency list.
+    ///
+    /// Since the graph is undirected, an edge contributes one to the degree of each of its two end nodes.
+    /// Parallel edges are counted individually. Nodes that don't exist in the graph have degree ```0```.
+    pub fn degree(&self, node: usize) -> usize {
+        self.weights.get(&node).map_or(0, |nb| nb.len())
     }
 
-    /// Finds the shortest paths from a source node to destination nodes.
+    /// Returns the maximum degree over all nodes in the graph, or ```0``` if the graph is empty.
+    pub fn max_degree(&self) -> usize {
+        self.weights.values().map(|nb| nb.len()).max().unwrap_or(0)
+    }
+
+    /// Returns the average degree over all nodes in the graph, or ```0.0``` if the graph is empty.
+    pub fn mean_degree(&self) -> f64 {
+        if self.weights.is_empty() {
+            return 0.0;
+        }
+
+        let sum: usize = self.weights.values().map(|nb| nb.len()).sum();
+        sum as f64 / self.weights.len() as f64
+    }
+
+    /// Returns the degree distribution of the graph as a list of ```(degree, number of nodes)```,
+    /// sorted by degree in ascending order.
+    pub fn degree_histogram(&self) -> Vec<(usize, usize)> {
+        let mut counts = HashMap::new();
+
+        for nb in self.weights.values() {
+            *counts.entry(nb.len()).or_insert(0) += 1;
+        }
+
+        let mut hist: Vec<_> = counts.into_iter().collect();
+        hist.sort_unstable();
+        hist
+    }
+
+    /// Checks whether every node of the graph can be reached from every other node.
     ///
-    /// If you want to keep the result for later usage and/or want to save memory, consider using
-    /// the lazy version [`SimpleGraph::sssp_dijkstra_lazy`], which returns the intermediate result
-    /// from Dijkstra's algorithm.
-    pub fn sssp_dijkstra(&self, src: usize, dest: &[usize]) -> Vec<ShortestPath<W>>
-    where
-        W: Bounded + Num + Zero + PartialOrd + Copy,
-    {
-        let nodes = self.dijkstra(src);
-        let mut result = Vec::with_capacity(dest.len());
+    /// For graphs with directed arcs, this checks whether all nodes are reachable from the node with the
+    /// smallest index. An empty graph is considered connected.
+    pub fn is_connected(&self) -> bool {
+        let start = match self.weights.keys().min() {
+            Some(start) => *start,
+            None => return true,
+        };
 
-        for ii in dest {
-            result.push(traverse_path(src, *ii, &nodes));
+        let mut visited = vec![false; self.node_bound()];
+        let mut stack = vec![start];
+        let mut count = 0;
+        visited[start] = true;
+
+        while let Some(node) = stack.pop() {
+            count += 1;
+
+            for (u, _) in self.neighbours(node) {
+                if !visited[u] {
+                    visited[u] = true;
+                    stack.push(u);
+                }
+            }
         }
 
-        result
+        count == self.weights.len()
     }
 
-    /// Finds the shortest paths from a source node to all nodes and returns the intermediate result
-    /// for later usage.
-    pub fn sssp_dijkstra_lazy(&self, src: usize) -> LazyShortestPaths<W>
-    where
-        W: Bounded + Num + Zero + PartialOrd + Copy,
-    {
-        LazyShortestPaths {
-            src,
-            paths: self.dijkstra(src),
+    /// Checks whether ```dest``` can be reached from ```src```.
+    ///
+    /// Unlike the shortest path searches, this runs a breadth-first search that ignores the weights and stops
+    /// as soon as ```dest``` is found. Only the visited nodes are tracked, so a query between nearby nodes
+    /// doesn't pay for the size of the graph. Returns ```false``` if either node is not in the graph.
+    pub fn is_reachable(&self, src: usize, dest: usize) -> bool {
+        if !self.has_node(src) || !self.has_node(dest) {
+            return false;
+        }
+
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(src);
+        queue.push_back(src);
+
+        while let Some(node) = queue.pop_front() {
+            if node == dest {
+                return true;
+            }
+
+            for (u, _) in self.neighbours(node) {
+                if visited.insert(u) {
+                    queue.push_back(u);
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Returns the set of nodes that can be reached from ```src```, including ```src``` itself.
+    ///
+    /// The set is empty if ```src``` is not in the graph.
+    pub fn reachable_set(&self, src: usize) -> HashSet<usize> {
+        let mut visited = HashSet::new();
+        if !self.has_node(src) {
+            return visited;
+        }
+
+        let mut stack = vec![src];
+        visited.insert(src);
+
+        while let Some(node) = stack.pop() {
+            for (u, _) in self.neighbours(node) {
+                if visited.insert(u) {
+                    stack.push(u);
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// Returns basic statistics of the graph.
+    ///
+    /// The degree statistics are computed in one pass over the adjacency lists, the connectivity with a
+    /// traversal of the graph. See [`SimpleGraph::degree`] for how degrees are counted.
+    pub fn stats(&self) -> GraphStats {
+        let mut min_degree = usize::MAX;
+        let mut max_degree = 0;
+        let mut sum = 0;
+
+        for nb in self.weights.values() {
+            min_degree = min_degree.min(nb.len());
+            max_degree = max_degree.max(nb.len());
+            sum += nb.len();
+        }
+
+        let n_nodes = self.weights.len();
+
+        GraphStats {
+            n_nodes,
+            n_edges: self.n_edges,
+            min_degree: if n_nodes == 0 { 0 } else { min_degree },
+            max_degree,
+            mean_degree: if n_nodes == 0 {
+                0.0
+            } else {
+                sum as f64 / n_nodes as f64
+            },
+            density: if n_nodes < 2 {
+                0.0
+            } else {
+                sum as f64 / (n_nodes * (n_nodes - 1)) as f64
+            },
+            connected: self.is_connected(),
+        }
+    }
+
+    /// Adds a node without any edges to the graph.
+    ///
+    /// If the node already exists in the graph, nothing happens.
+    pub fn add_node(&mut self, node: usize) {
+        self.adjacency_mut(node);
+    }
+
+    /// Adds a weighted edge to the graph.
+    ///
+    /// If the edge already exists in the graph, the weight will be updated. An edge from a node to itself
+    /// is skipped and not counted.
+    ///
+    /// # Panics
+    /// Panics if the weight is not comparable to itself, i.e. ```NaN```, which would break the ordering
+    /// of all algorithms on the graph. Use [`SimpleGraph::add_weighted_edges_checked`] to handle such
+    /// weights as an error instead.
+    pub fn add_weighted_edges(&mut self, node1: usize, node2: usize, weight: W)
+    where
+        W: Copy + PartialOrd,
+    {
+        self.add_or_resolve_edge(node1, node2, weight, |_, w| w);
+    }
+
+    /// Adds a weighted edge to the graph. If an arc of the edge already exists, its weight is replaced by
+    /// ```resolve(existing weight, weight)```.
+    fn add_or_resolve_edge<F>(&mut self, node1: usize, node2: usize, weight: W, resolve: F)
+    where
+        W: Copy + PartialOrd,
+        F: Fn(W, W) -> W,
+    {
+        assert_comparable(node1, node2, &weight);
+
+        if node1 != node2 {
+            for (from, to) in [(node1, node2), (node2, node1)] {
+                if self.upsert_weight(from, to, weight, &resolve) {
+                    self.n_edges += 1;
+                }
+            }
+        }
+    }
+
+    /// Adds an edge with the weight ```1``` to the graph, e.g. for unweighted graphs.
+    ///
+    /// See [`SimpleGraph::sssp_bfs`] for the shortest paths in terms of hop counts.
+    pub fn add_edge(&mut self, node1: usize, node2: usize)
+    where
+        W: One + Copy + PartialOrd,
+    {
+        self.add_weighted_edges(node1, node2, W::one());
+    }
+
+    /// Adds a weighted edge with a payload to the graph.
+    ///
+    /// The payload belongs to the pair of nodes, in both directions, and replaces the payload of a previous
+    /// edge between them. Like in [`SimpleGraph::add_weighted_edges`], an edge from a node to itself is
+    /// skipped.
+    ///
+    /// # Panics
+    /// Panics if the weight is ```NaN```, like [`SimpleGraph::add_weighted_edges`].
+    pub fn add_weighted_edge_with(&mut self, node1: usize, node2: usize, weight: W, data: E)
+    where
+        W: Copy + PartialOrd,
+    {
+        self.add_weighted_edges(node1, node2, weight);
+
+        if node1 != node2 {
+            self.edge_data.insert(edge_key(node1, node2), data);
+        }
+    }
+
+    /// Returns the payload of the edge between two nodes, or ```None``` if the edge has no payload.
+    #[inline]
+    pub fn edge_data(&self, node1: usize, node2: usize) -> Option<&E> {
+        self.edge_data.get(&edge_key(node1, node2))
+    }
+
+    /// Adds a weighted edge to the graph after validating it.
+    ///
+    /// Unlike [`SimpleGraph::add_weighted_edges`], which silently skips an edge from a node to itself,
+    /// this returns [`GraphError::SelfLoop`] for it. A weight that is not comparable to itself, such as
+    /// ```NaN```, would break the ordering of the shortest path algorithms and is rejected with
+    /// [`GraphError::InvalidWeight`]. The graph is only modified if the edge is valid.
+    pub fn add_weighted_edges_checked(
+        &mut self,
+        node1: usize,
+        node2: usize,
+        weight: W,
+    ) -> Result<(), GraphError>
+    where
+        W: Copy + PartialOrd,
+    {
+        if node1 == node2 {
+            return Err(GraphError::SelfLoop(node1));
+        }
+
+        if weight.partial_cmp(&weight).is_none() {
+            return Err(GraphError::InvalidWeight(node1, node2));
+        }
+
+        self.add_weighted_edges(node1, node2, weight);
+        Ok(())
+    }
+
+    /// Adds a weighted, directed arc from ```from``` to ```to``` to the graph.
+    ///
+    /// Unlike [`SimpleGraph::add_weighted_edges`], the arc is only stored in the adjacency list of
+    /// ```from```. Both nodes are registered in the graph.
+    ///
+    /// # Panics
+    /// Panics if the weight is ```NaN```, like [`SimpleGraph::add_weighted_edges`].
+    pub fn add_weighted_arc(&mut self, from: usize, to: usize, weight: W)
+    where
+        W: PartialOrd,
+    {
+        assert_comparable(from, to, &weight);
+        self.add_node(to);
+        self.insert_weight(from, to, weight);
+        self.n_edges += 1;
+    }
+
+    /// Changes the weight of the edge between two nodes to ```weight```.
+    ///
+    /// An undirected edge is changed in both directions, an arc only from ```node1``` to ```node2```. Of
+    /// parallel edges, only the first one added is changed. Returns ```false``` and leaves the graph
+    /// unchanged if there is no such edge.
+    ///
+    /// # Panics
+    /// Panics if the weight is ```NaN```, like [`SimpleGraph::add_weighted_edges`].
+    pub fn set_edge_weight(&mut self, node1: usize, node2: usize, weight: W) -> bool
+    where
+        W: Copy + PartialOrd,
+    {
+        assert_comparable(node1, node2, &weight);
+        let mut found = false;
+
+        for &(a, b) in &[(node1, node2), (node2, node1)] {
+            if let Some(w) = self.edge_weight_mut(a, b) {
+                *w = weight;
+                found = true;
+            }
+        }
+
+        found
+    }
+
+    /// Collapses parallel edges between the same pair of nodes into one edge with the minimum weight.
+    pub fn dedup_edges(&mut self)
+    where
+        W: Copy + PartialOrd,
+    {
+        self.dedup_edges_with(|w1, w2| if w2 < w1 { w2 } else { w1 });
+    }
+
+    /// Collapses parallel edges between the same pair of nodes into one edge.
+    ///
+    /// The weights of parallel edges are folded in the order the edges were added with
+    /// ```combine(accumulated weight, next weight)```. For example, ```|_, w| w``` keeps the weight of
+    /// the last added edge. The deduplicated edge keeps the position of the first added edge.
+    pub fn dedup_edges_with<F>(&mut self, mut combine: F)
+    where
+        W: Copy,
+        F: FnMut(W, W) -> W,
+    {
+        let mut removed = 0;
+
+        for nb in self.weights.values_mut() {
+            let mut pos: HashMap<usize, usize> = HashMap::with_capacity(nb.len());
+            let mut deduped: Vec<(usize, W)> = Vec::with_capacity(nb.len());
+
+            for (idx, w) in nb.drain(..) {
+                match pos.get(&idx) {
+                    Some(p) => {
+                        let acc = &mut deduped[*p].1;
+                        *acc = combine(*acc, w);
+                        removed += 1;
+                    }
+                    None => {
+                        pos.insert(idx, deduped.len());
+                        deduped.push((idx, w));
+                    }
+                }
+            }
+
+            *nb = deduped;
+        }
+
+        self.n_edges -= removed;
+    }
+
+    /// Checks whether a node exists in the graph.
+    pub fn has_node(&self, node: usize) -> bool {
+        self.weights.contains_key(&node)
+    }
+
+    /// Checks whether an edge between two nodes exists.
+    ///
+    /// The lookup scans the adjacency list of ```node1``` and thus runs in ```O(degree)```, or in
+    /// ```O(log degree)``` after [`SimpleGraph::finalize`].
+    pub fn has_edge(&self, node1: usize, node2: usize) -> bool {
+        self.edge_weight(node1, node2).is_some()
+    }
+
+    /// Returns the weight of the edge between two nodes, or ```None``` if no such edge exists.
+    ///
+    /// If there are parallel edges between the two nodes, the weight of the first one added is returned.
+    pub fn edge_weight(&self, node1: usize, node2: usize) -> Option<&W> {
+        let nb = self.weights.get(&node1)?;
+
+        if self.sorted {
+            // The sort is stable, so the first entry of a run of parallel edges is the first one added.
+            let pos = nb.partition_point(|(idx, _)| *idx < node2);
+            nb.get(pos).filter(|(idx, _)| *idx == node2).map(|(_, w)| w)
+        } else {
+            nb.iter().find(|(idx, _)| *idx == node2).map(|(_, w)| w)
+        }
+    }
+
+    /// Returns an iterator over the neighbours of a node and the weights of the connecting edges.
+    ///
+    /// If the node doesn't exist in the graph, the iterator is empty.
+    #[inline]
+    pub fn neighbours(&self, node: usize) -> impl Iterator<Item = (usize, &W)> + '_ {
+        self.weights
+            .get(&node)
+            .into_iter()
+            .flatten()
+            .map(|(idx, w)| (*idx, w))
+    }
+
+    /// Finds the shortest paths from a source node to destination nodes.
+    ///
+    /// If you want to keep the result for later usage and/or want to save memory, consider using
+    /// the lazy version [`SimpleGraph::sssp_dijkstra_lazy`], which returns the intermediate result
+    /// from Dijkstra's algorithm.
+    pub fn sssp_dijkstra(&self, src: usize, dest: &[usize]) -> Vec<ShortestPath<W>>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let nodes = self.dijkstra(src);
+        let mut result = Vec::with_capacity(dest.len());
+
+        for ii in dest {
+            result.push(traverse_path(src, *ii, &nodes));
+        }
+
+        result
+    }
+
+    /// Same as [`SimpleGraph::sssp_dijkstra`], but checks the node indices first.
+    ///
+    /// Returns [`GraphError::EmptyGraph`] if the graph has no nodes, and [`GraphError::NodeNotFound`]
+    /// for the first of the source and destination nodes that doesn't exist in the graph.
+    pub fn try_sssp_dijkstra(
+        &self,
+        src: usize,
+        dest: &[usize],
+    ) -> Result<Vec<ShortestPath<W>>, GraphError>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        self.check_node(src)?;
+        for node in dest {
+            self.check_node(*node)?;
+        }
+
+        Ok(self.sssp_dijkstra(src, dest))
+    }
+
+    /// Finds the shortest path from a source node to the nearest of several target nodes.
+    ///
+    /// The search ends as soon as the first target node is settled, so that the rest of the graph is
+    /// not explored. If the source node is a target itself, the path consists of the source node only.
+    ///
+    /// If no target node is reachable from the source node, the returned path is infeasible and its
+    /// destination is the first target node, or the source node if there are no target nodes.
+    pub fn sssp_dijkstra_any(&self, src: usize, targets: &[usize]) -> ShortestPath<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let mut stop = StopAtAny {
+            targets: vec![false; self.node_bound()],
+            reached: None,
+        };
+        for target in targets {
+            if let Some(flag) = stop.targets.get_mut(*target) {
+                *flag = true;
+            }
+        }
+
+        let mut buffer = DijkstraBuffer::new();
+        self.dijkstra_with_buffer(
+            src,
+            None,
+            DijkstraStrategy::default(),
+            &mut buffer,
+            &mut stop,
+        );
+
+        match stop.reached {
+            Some(dest) => traverse_path(src, dest, &buffer.nodes),
+            None => ShortestPath::infeasible(src, targets.first().copied().unwrap_or(src)),
+        }
+    }
+
+    /// Finds the shortest paths from a source node to all nodes and returns the intermediate result
+    /// for later usage.
+    pub fn sssp_dijkstra_lazy(&self, src: usize) -> LazyShortestPaths<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        LazyShortestPaths {
+            src,
+            paths: self.dijkstra(src),
+        }
+    }
+
+    /// Finds the paths with the fewest edges from a source node to all nodes, ignoring the weights.
+    ///
+    /// The distances are hop counts. A breadth-first search with a plain queue is used instead of a
+    /// priority queue, which takes ```O(V + E)``` time. On a graph in which all weights are ```1```, the
+    /// distances agree with [`SimpleGraph::sssp_dijkstra_lazy`].
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::graph::SimpleGraph;
+    ///
+    /// let mut g = SimpleGraph::<u32>::new();
+    /// g.add_edge(0, 1);
+    /// g.add_edge(1, 2);
+    /// g.add_edge(0, 3);
+    /// g.add_edge(3, 2);
+    /// g.add_node(4);
+    ///
+    /// let lsp = g.sssp_bfs(0);
+    /// assert_eq!(Some(2), lsp.dist(2));
+    /// assert_eq!(None, lsp.dist(4));
+    /// ```
+    pub fn sssp_bfs(&self, src: usize) -> LazyShortestPaths<usize> {
+        let n_nodes = self.node_bound();
+        assert!(src < n_nodes, "source node {} is not in the graph", src);
+
+        let mut nodes = vec![DijNode::<usize>::new(); n_nodes];
+        nodes[src].dist = 0;
+        nodes[src].visited = true;
+
+        let mut queue = VecDeque::new();
+        queue.push_back(src);
+
+        while let Some(node) = queue.pop_front() {
+            let hops = nodes[node].dist + 1;
+
+            for (u, _) in self.neighbours(node) {
+                let bfsnode = &mut nodes[u];
+                if !bfsnode.visited {
+                    bfsnode.dist = hops;
+                    bfsnode.pred = node;
+                    bfsnode.len = hops;
+                    bfsnode.feasible = true;
+                    bfsnode.visited = true;
+                    queue.push_back(u);
+                }
+            }
+        }
+
+        LazyShortestPaths { src, paths: nodes }
+    }
+
+    /// Same as [`SimpleGraph::sssp_dijkstra_lazy`], but with the given [`DijkstraStrategy`] for
+    /// updating the priority queue.
+    ///
+    /// Both strategies return the same paths, see [`DijkstraStrategy`]. This function is mainly useful for
+    /// comparing their performance.
+    pub fn sssp_dijkstra_lazy_with_strategy(
+        &self,
+        src: usize,
+        strategy: DijkstraStrategy,
+    ) -> LazyShortestPaths<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        self.sssp_dijkstra_visit_with_strategy(src, strategy, &mut NoopVisitor)
+    }
+
+    /// Finds the shortest paths from every node of the graph to all nodes in parallel.
+    ///
+    /// The element at index ```i``` of the returned vector is the result for source node ```i```.
+    #[cfg(feature = "rayon")]
+    pub fn apsp_dijkstra_par(&self) -> Vec<LazyShortestPaths<W>>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy + Send + Sync,
+        E: Sync,
+    {
+        let sources: Vec<usize> = (0..self.node_bound()).collect();
+        self.sssp_dijkstra_many_par(&sources)
+    }
+
+    /// Finds the shortest paths from each of the given source nodes to all nodes in parallel, with one
+    /// call of [`SimpleGraph::sssp_dijkstra_lazy`] per source node.
+    ///
+    /// The results are returned in the same order as the source nodes.
+    #[cfg(feature = "rayon")]
+    pub fn sssp_dijkstra_many_par(&self, sources: &[usize]) -> Vec<LazyShortestPaths<W>>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy + Send + Sync,
+        E: Sync,
+    {
+        use rayon::prelude::*;
+
+        sources
+            .par_iter()
+            .map(|&src| self.sssp_dijkstra_lazy(src))
+            .collect()
+    }
+
+    /// Returns the eccentricity of a node, i.e. the largest distance from it to any other node.
+    ///
+    /// Returns ```None``` if the node is not in the graph or some node cannot be reached from it.
+    pub fn eccentricity(&self, node: usize) -> Option<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        if !self.has_node(node) {
+            return None;
+        }
+
+        let lsp = self.sssp_dijkstra_lazy(node);
+        let mut ecc = W::zero();
+
+        for idx in self.weights.keys() {
+            let dist = lsp.dist(*idx)?;
+            if dist > ecc {
+                ecc = dist;
+            }
+        }
+
+        Some(ecc)
+    }
+
+    /// Returns the diameter of the graph, i.e. the largest eccentricity of all nodes.
+    ///
+    /// This runs Dijkstra's algorithm from every node. Returns ```None``` if the graph is empty or some
+    /// node cannot be reached from another one. With the ```rayon``` feature, ```diameter_par``` is a
+    /// parallel version.
+    pub fn diameter(&self) -> Option<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        max_eccentricity(self.weights.keys().map(|node| self.eccentricity(*node)))
+    }
+
+    /// Returns the radius of the graph, i.e. the smallest eccentricity of all nodes.
+    ///
+    /// This runs Dijkstra's algorithm from every node. Returns ```None``` if no node reaches all other
+    /// nodes, which includes empty and disconnected graphs. With the ```rayon``` feature, ```radius_par```
+    /// is a parallel version.
+    pub fn radius(&self) -> Option<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        min_eccentricity(self.weights.keys().map(|node| self.eccentricity(*node)))
+    }
+
+    /// Returns the diameter of the graph, computing the eccentricities of the nodes in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn diameter_par(&self) -> Option<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy + Send + Sync,
+        E: Sync,
+    {
+        max_eccentricity(self.eccentricities_par())
+    }
+
+    /// Returns the radius of the graph, computing the eccentricities of the nodes in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn radius_par(&self) -> Option<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy + Send + Sync,
+        E: Sync,
+    {
+        min_eccentricity(self.eccentricities_par())
+    }
+
+    #[cfg(feature = "rayon")]
+    fn eccentricities_par(&self) -> Vec<Option<W>>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy + Send + Sync,
+        E: Sync,
+    {
+        use rayon::prelude::*;
+
+        let nodes: Vec<usize> = self.weights.keys().copied().collect();
+        nodes
+            .par_iter()
+            .map(|node| self.eccentricity(*node))
+            .collect()
+    }
+
+    /// Computes the betweenness centrality of every node with Brandes' algorithm.
+    ///
+    /// The score of a node is the sum, over all ordered pairs of other nodes, of the fraction of shortest
+    /// paths between the pair that pass through the node. Each undirected edge is stored as two arcs, so
+    /// both directions of a pair are counted; halve the scores to get the usual values for undirected
+    /// graphs. The scores are not normalized and are indexed by node. Weights must be positive.
+    ///
+    /// One Dijkstra search is run per node, followed by an accumulation of the pair dependencies over the
+    /// shortest path DAG in reverse settle order. The accumulation takes ```O(V * E)``` time in total, the
+    /// searches ```O(V * E log V)```.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pheap::graph::SimpleGraph;
+    ///
+    /// let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (1, 2, 1)]);
+    /// assert_eq!(vec![0.0, 2.0, 0.0], g.betweenness_centrality());
+    /// ```
+    pub fn betweenness_centrality(&self) -> Vec<f64>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let n_nodes = self.node_bound();
+        let mut centrality = vec![0.0; n_nodes];
+        // Number of shortest paths from the source node and dependency of the source node on each node.
+        let mut sigma = vec![0.0; n_nodes];
+        let mut delta = vec![0.0; n_nodes];
+
+        for src in self.weights.keys().copied() {
+            let (lsp, order) = self.sssp_dijkstra_traced(src);
+            // Checks whether the arc from u to v lies on a shortest path from the source node.
+            let on_path = |u: usize, v: usize, w: W| match (lsp.dist(u), lsp.dist(v)) {
+                (Some(du), Some(dv)) => v != src && du + w == dv,
+                _ => false,
+            };
+
+            for node in &order {
+                sigma[*node] = 0.0;
+                delta[*node] = 0.0;
+            }
+            sigma[src] = 1.0;
+
+            for u in order.iter().copied() {
+                for (v, w) in self.neighbours(u) {
+                    if on_path(u, v, *w) {
+                        sigma[v] += sigma[u];
+                    }
+                }
+            }
+
+            for u in order.iter().rev().copied() {
+                for (v, w) in self.neighbours(u) {
+                    if on_path(u, v, *w) {
+                        delta[u] += sigma[u] / sigma[v] * (1.0 + delta[v]);
+                    }
+                }
+
+                if u != src {
+                    centrality[u] += delta[u];
+                }
+            }
+        }
+
+        centrality
+    }
+
+    /// Computes the local clustering coefficient of a node, i.e. the fraction of pairs of its neighbours
+    /// that are connected by an edge themselves.
+    ///
+    /// Weights are ignored and parallel edges count as a single edge. A pair of neighbours counts as
+    /// connected if there is an arc in either direction between them. Returns ```0``` for nodes with fewer
+    /// than two neighbours and nodes not in the graph.
+    pub fn clustering_coefficient(&self, node: usize) -> f64 {
+        let nb: HashSet<usize> = self.neighbours(node).map(|(u, _)| u).collect();
+        let k = nb.len() - usize::from(nb.contains(&node));
+        if k < 2 {
+            return 0.0;
+        }
+
+        let mut links = HashSet::new();
+        for u in nb.iter().copied().filter(|u| *u != node) {
+            for (v, _) in self.neighbours(u) {
+                if v != u && v != node && nb.contains(&v) {
+                    links.insert((u.min(v), u.max(v)));
+                }
+            }
+        }
+
+        2.0 * links.len() as f64 / (k * (k - 1)) as f64
+    }
+
+    /// Returns the mean of the local clustering coefficients of all nodes, see
+    /// [`SimpleGraph::clustering_coefficient`], or ```0``` for an empty graph.
+    pub fn average_clustering(&self) -> f64 {
+        if self.weights.is_empty() {
+            return 0.0;
+        }
+
+        let total: f64 = self
+            .weights
+            .keys()
+            .map(|node| self.clustering_coefficient(*node))
+            .sum();
+        total / self.weights.len() as f64
+    }
+
+    /// Computes the closeness centrality of a node, i.e. the reciprocal of the sum of the distances from it
+    /// to all other nodes.
+    ///
+    /// In a disconnected graph, only the nodes reachable from ```node``` are summed up. The score is not
+    /// scaled by the number of reachable nodes, so that a node in a small component can score higher than
+    /// a central node in a large one. Returns ```0``` if no other node is reachable or the node is not in
+    /// the graph.
+    pub fn closeness_centrality(&self, node: usize) -> f64
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy + ToPrimitive,
+    {
+        if !self.has_node(node) {
+            return 0.0;
+        }
+
+        let total: f64 = self
+            .sssp_dijkstra_lazy(node)
+            .distances()
+            .into_iter()
+            .flatten()
+            .map(|dist| dist.to_f64().unwrap_or(0.0))
+            .sum();
+
+        if total > 0.0 {
+            1.0 / total
+        } else {
+            0.0
+        }
+    }
+
+    /// Computes the closeness centrality of every node, see [`SimpleGraph::closeness_centrality`].
+    ///
+    /// The scores are indexed by node. Indices that are not nodes of the graph have a score of ```0```.
+    pub fn closeness_centrality_all(&self) -> Vec<f64>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy + ToPrimitive,
+    {
+        let mut centrality = vec![0.0; self.node_bound()];
+        for node in self.weights.keys() {
+            centrality[*node] = self.closeness_centrality(*node);
+        }
+
+        centrality
+    }
+
+    /// Computes the PageRank of every node by weighted power iteration.
+    ///
+    /// In each step, a node passes the fraction ```damping``` of its score on to its neighbours, in
+    /// proportion to the weights of the connecting edges, and the rest is spread evenly over all nodes.
+    /// The score of a dangling node, which has no outgoing edges, is spread evenly over all nodes. The
+    /// iteration stops when the scores change by less than ```tol``` in the L1 norm, or after
+    /// ```max_iter``` steps. Weights must not be negative.
+    ///
+    /// The scores are indexed by node and sum up to ```1```. Indices that are not nodes of the graph have
+    /// a score of ```0```.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pheap::graph::SimpleGraph;
+    ///
+    /// let mut g = SimpleGraph::<u32>::new();
+    /// g.add_weighted_arc(0, 1, 1);
+    /// g.add_weighted_arc(1, 2, 1);
+    /// g.add_weighted_arc(2, 0, 1);
+    ///
+    /// let ranks = g.pagerank(0.85, 1e-9, 100);
+    /// assert!(ranks.iter().all(|r| (r - 1.0 / 3.0).abs() < 1e-6));
+    /// ```
+    pub fn pagerank(&self, damping: f64, tol: f64, max_iter: usize) -> Vec<f64>
+    where
+        W: ToPrimitive,
+    {
+        let n_nodes = self.node_bound();
+        let mut ranks = vec![0.0; n_nodes];
+        if self.weights.is_empty() {
+            return ranks;
+        }
+
+        let share = 1.0 / self.weights.len() as f64;
+        let mut out_weights = vec![0.0; n_nodes];
+
+        for (node, nb) in &self.weights {
+            ranks[*node] = share;
+            out_weights[*node] = nb.iter().map(|(_, w)| w.to_f64().unwrap_or(0.0)).sum();
+        }
+
+        let mut next = vec![0.0; n_nodes];
+
+        for _ in 0..max_iter {
+            let dangling: f64 = self
+                .weights
+                .keys()
+                .filter(|node| out_weights[**node] <= 0.0)
+                .map(|node| ranks[*node])
+                .sum();
+            let base = (1.0 - damping + damping * dangling) * share;
+
+            for node in self.weights.keys() {
+                next[*node] = base;
+            }
+
+            for (node, nb) in &self.weights {
+                if out_weights[*node] > 0.0 {
+                    let rank = damping * ranks[*node] / out_weights[*node];
+                    for (idx, w) in nb {
+                        next[*idx] += rank * w.to_f64().unwrap_or(0.0);
+                    }
+                }
+            }
+
+            let change: f64 = self
+                .weights
+                .keys()
+                .map(|node| (next[*node] - ranks[*node]).abs())
+                .sum();
+            std::mem::swap(&mut ranks, &mut next);
+
+            if change < tol {
+                break;
+            }
+        }
+
+        ranks
+    }
+
+    /// Sorts the nodes of the graph topologically, following the direction of the arcs.
+    ///
+    /// Returns [`NotADag`], which names a node on a cycle, if the graph contains a cycle. Note that an
+    /// undirected edge added with [`SimpleGraph::add_weighted_edges`] is a cycle of length two.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, NotADag> {
+        let mut in_degree = vec![0usize; self.node_bound()];
+        for nb in self.weights.values() {
+            for (idx, _) in nb {
+                in_degree[*idx] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<_> = (0..in_degree.len())
+            .filter(|ii| in_degree[*ii] == 0 && self.weights.contains_key(ii))
+            .collect();
+        let mut order = Vec::with_capacity(self.weights.len());
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+
+            for (u, _) in self.neighbours(node) {
+                in_degree[u] -= 1;
+                if in_degree[u] == 0 {
+                    queue.push_back(u);
+                }
+            }
+        }
+
+        if order.len() == self.weights.len() {
+            Ok(order)
+        } else {
+            Err(NotADag {
+                node: self.find_cycle_node(&in_degree),
+            })
+        }
+    }
+
+    /// Returns a node on a cycle among the nodes that a topological sort left with a positive in-degree.
+    ///
+    /// Besides the nodes on cycles, these include all nodes reachable from a cycle. Every one of them has a
+    /// predecessor among them, so a depth-first search over them runs into a node on its current path.
+    fn find_cycle_node(&self, in_degree: &[usize]) -> usize {
+        // 0: not visited, 1: on the current path, 2: finished.
+        let mut state = vec![0u8; in_degree.len()];
+
+        for start in (0..in_degree.len()).filter(|ii| in_degree[*ii] > 0) {
+            if state[start] != 0 {
+                continue;
+            }
+
+            state[start] = 1;
+            let mut stack = vec![(start, 0)];
+
+            while let Some((node, next)) = stack.last_mut() {
+                let node = *node;
+                match self.weights[&node].get(*next) {
+                    Some((u, _)) => {
+                        *next += 1;
+                        let u = *u;
+                        if in_degree[u] == 0 {
+                            continue;
+                        }
+
+                        match state[u] {
+                            0 => {
+                                state[u] = 1;
+                                stack.push((u, 0));
+                            }
+                            1 => return u,
+                            _ => {}
+                        }
+                    }
+                    None => {
+                        state[node] = 2;
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        unreachable!("the remaining nodes contain a cycle")
+    }
+
+    /// Finds the shortest paths from a source node to all nodes in a directed acyclic graph (DAG).
+    ///
+    /// The edges are relaxed in topological order, which takes ```O(V + E)``` time and, unlike
+    /// Dijkstra's algorithm, also supports negative weights. Returns [`NotADag`] if the graph
+    /// contains a cycle.
+    pub fn dag_shortest_path(&self, src: usize) -> Result<LazyShortestPaths<W>, NotADag>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let n_nodes = self.node_bound();
+        assert!(src < n_nodes, "source node {} is not in the graph", src);
+        let order = self.topological_sort()?;
+
+        let mut nodes = vec![DijNode::<W>::new(); n_nodes];
+        nodes[src].dist = W::zero();
+
+        for node in order {
+            if node != src && !nodes[node].feasible {
+                continue;
+            }
+
+            let prio = nodes[node].dist;
+            let count = nodes[node].len + 1;
+
+            for (u, dist) in self.neighbours(node) {
+                let dagnode = &mut nodes[u];
+                let alt = prio + *dist;
+                // Unreached nodes start with an infinite distance for floating point weights, so that an
+                // infinite path never makes a node feasible.
+                if alt < dagnode.dist {
+                    dagnode.dist = alt;
+                    dagnode.pred = node;
+                    dagnode.len = count;
+                    dagnode.feasible = true;
+                }
+            }
+
+            nodes[node].visited = true;
+        }
+
+        Ok(LazyShortestPaths { src, paths: nodes })
+    }
+
+    /// Finds the shortest paths from a source node to all nodes with the Bellman-Ford algorithm.
+    ///
+    /// Unlike Dijkstra's algorithm, negative weights are supported, at the cost of ```O(V * E)``` time.
+    /// Returns a [`NegativeCycle`] if a cycle of negative total weight is reachable from the source node.
+    /// Note that an undirected edge with a negative weight is such a cycle.
+    pub fn sssp_bellman_ford(&self, src: usize) -> Result<LazyShortestPaths<W>, NegativeCycle>
+    where
+        W: Num + PartialOrd + Copy,
+    {
+        let n_nodes = self.node_bound().max(src + 1);
+        let mut nodes = vec![
+            DijNode {
+                pred: 0,
+                len: 0,
+                visited: false,
+                feasible: false,
+                dist: W::zero(),
+            };
+            n_nodes
+        ];
+        nodes[src].visited = true;
+
+        // Without a negative cycle, all shortest paths are found after n_nodes - 1 rounds. A change in
+        // the last round thus proves that there is one.
+        let mut last_changed = None;
+
+        for _ in 0..n_nodes {
+            last_changed = None;
+
+            for node in 0..n_nodes {
+                if !nodes[node].visited {
+                    continue;
+                }
+
+                let prio = nodes[node].dist;
+                let count = nodes[node].len + 1;
+
+                for (u, dist) in self.neighbours(node) {
+                    let bfnode = &mut nodes[u];
+                    let alt = prio + *dist;
+                    if !bfnode.visited || alt < bfnode.dist {
+                        bfnode.dist = alt;
+                        bfnode.pred = node;
+                        bfnode.len = count;
+                        bfnode.feasible = true;
+                        bfnode.visited = true;
+                        last_changed = Some(u);
+                    }
+                }
+            }
+
+            if last_changed.is_none() {
+                break;
+            }
+        }
+
+        match last_changed {
+            None => Ok(LazyShortestPaths { src, paths: nodes }),
+            Some(mut node) => {
+                // Following the predecessors long enough is guaranteed to end up on the cycle.
+                for _ in 0..n_nodes {
+                    node = nodes[node].pred;
+                }
+
+                let mut cycle = vec![node];
+                let mut next = nodes[node].pred;
+                while next != node {
+                    cycle.push(next);
+                    next = nodes[next].pred;
+                }
+                cycle.reverse();
+
+                Err(NegativeCycle { cycle })
+            }
+        }
+    }
+
+    /// Checks whether any edge of the graph has a negative weight.
+    pub fn has_negative_edge(&self) -> bool
+    where
+        W: Zero + PartialOrd,
+    {
+        let zero = W::zero();
+        self.weights
+            .values()
+            .any(|nb| nb.iter().any(|(_, w)| *w < zero))
+    }
+
+    /// Finds the shortest paths from a source node to all nodes with Dijkstra's algorithm if all weights are
+    /// non-negative, and with the Bellman-Ford algorithm otherwise.
+    ///
+    /// Dijkstra's algorithm silently returns wrong distances for negative weights, which this function
+    /// rules out at the cost of one pass over all edges. See [`SimpleGraph::sssp_bellman_ford`] for the
+    /// errors.
+    pub fn shortest_paths(&self, src: usize) -> Result<LazyShortestPaths<W>, NegativeCycle>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        if self.has_negative_edge() {
+            self.sssp_bellman_ford(src)
+        } else {
+            Ok(self.sssp_dijkstra_lazy(src))
+        }
+    }
+
+    /// Finds the shortest paths from a source node to all nodes, reporting the progress of Dijkstra's
+    /// algorithm to a [`DijkstraVisitor`].
+    pub fn sssp_dijkstra_visit<V>(&self, src: usize, visitor: &mut V) -> LazyShortestPaths<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+        V: DijkstraVisitor<W> + ?Sized,
+    {
+        self.sssp_dijkstra_visit_with_strategy(src, DijkstraStrategy::default(), visitor)
+    }
+
+    /// Same as [`SimpleGraph::sssp_dijkstra_visit`], but with the given [`DijkstraStrategy`] for
+    /// updating the priority queue.
+    ///
+    /// Only [`DijkstraStrategy::Reinsert`] leaves outdated entries in the priority queue, which are reported
+    /// to [`DijkstraVisitor::on_skip`].
+    pub fn sssp_dijkstra_visit_with_strategy<V>(
+        &self,
+        src: usize,
+        strategy: DijkstraStrategy,
+        visitor: &mut V,
+    ) -> LazyShortestPaths<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+        V: DijkstraVisitor<W> + ?Sized,
+    {
+        let mut buffer = DijkstraBuffer::new();
+        self.dijkstra_with_buffer(src, None, strategy, &mut buffer, visitor);
+
+        LazyShortestPaths {
+            src,
+            paths: buffer.nodes,
+        }
+    }
+
+    /// Finds the shortest paths from a source node to all nodes and records the order in which the nodes
+    /// are settled, starting with the source node.
+    ///
+    /// Nodes that are not reachable from the source node don't appear in the settle order.
+    pub fn sssp_dijkstra_traced(&self, src: usize) -> (LazyShortestPaths<W>, Vec<usize>)
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let mut order = SettleOrder(Vec::new());
+        let lsp = self.sssp_dijkstra_visit(src, &mut order);
+        (lsp, order.0)
+    }
+
+    /// Finds all shortest paths from a source node and returns them as a DAG of predecessors.
+    ///
+    /// Each node reachable from the source node, except the source node itself, is mapped to all of its
+    /// neighbours through which a shortest path to it passes, sorted by index. Following the predecessors
+    /// from a node back to the source node enumerates every shortest path to it, whereas
+    /// [`LazyShortestPaths::get`] picks only one of them. Edges of zero weight may create cycles between nodes
+    /// of the same distance.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pheap::graph::SimpleGraph;
+    ///
+    /// let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (0, 2, 1), (1, 3, 1), (2, 3, 1)]);
+    /// let dag = g.shortest_path_dag(0);
+    ///
+    /// assert_eq!(Some(&vec![0]), dag.get(&1));
+    /// assert_eq!(Some(&vec![1, 2]), dag.get(&3));
+    /// ```
+    pub fn shortest_path_dag(&self, src: usize) -> HashMap<usize, Vec<usize>>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let lsp = self.sssp_dijkstra_lazy(src);
+        let mut dag: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for (node, nb) in &self.weights {
+            let prio = match lsp.dist(*node) {
+                Some(prio) => prio,
+                None => continue,
+            };
+
+            for (u, dist) in nb {
+                if *u != src && lsp.dist(*u) == Some(prio + *dist) {
+                    dag.entry(*u).or_default().push(*node);
+                }
+            }
+        }
+
+        for preds in dag.values_mut() {
+            preds.sort_unstable();
+            preds.dedup();
+        }
+
+        dag
+    }
+
+    /// Finds the shortest paths from a source node to all nodes within a maximum distance.
+    ///
+    /// The search stops as soon as the next node to be settled is farther away than ```max_dist```, so
+    /// that only the part of the graph around the source node is explored. Nodes at exactly ```max_dist```
+    /// are included. All other nodes are marked infeasible. The reachable nodes can be enumerated with
+    /// [`LazyShortestPaths::settled_nodes`].
+    pub fn sssp_dijkstra_within(&self, src: usize, max_dist: W) -> LazyShortestPaths<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let mut buffer = DijkstraBuffer::new();
+        self.dijkstra_with_buffer(
+            src,
+            Some(max_dist),
+            DijkstraStrategy::default(),
+            &mut buffer,
+            &mut NoopVisitor,
+        );
+
+        LazyShortestPaths {
+            src,
+            paths: buffer.nodes,
+        }
+    }
+
+    /// Finds the shortest paths from a source node to all nodes and passes them one by one to a callback.
+    ///
+    /// Unlike [`LazyShortestPaths::get_all`], the paths are never held in memory at the same time, which
+    /// makes this function suitable for processing every shortest path of a huge graph.
+    pub fn sssp_dijkstra_for_each<F>(&self, src: usize, mut f: F)
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+        F: FnMut(ShortestPath<W>),
+    {
+        let nodes = self.dijkstra(src);
+
+        for ii in 0..nodes.len() {
+            f(traverse_path(src, ii, &nodes));
+        }
+    }
+
+    /// Finds the shortest paths from a source node to destination nodes, reusing the memory of a
+    /// [`DijkstraBuffer`] for the intermediate result.
+    ///
+    /// This is useful when many queries are solved on the same graph, since the buffer only has to be
+    /// allocated once.
+    pub fn sssp_dijkstra_with_buffer(
+        &self,
+        src: usize,
+        dest: &[usize],
+        buffer: &mut DijkstraBuffer<W>,
+    ) -> Vec<ShortestPath<W>>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        self.dijkstra_with_buffer(
+            src,
+            None,
+            DijkstraStrategy::default(),
+            buffer,
+            &mut NoopVisitor,
+        );
+        let mut result = Vec::with_capacity(dest.len());
+
+        for ii in dest {
+            result.push(traverse_path(src, *ii, &buffer.nodes));
+        }
+
+        result
+    }
+
+    /// Finds up to ```k``` shortest paths without repeated nodes from ```src``` to ```dest```, using Yen's
+    /// algorithm.
+    ///
+    /// The paths are sorted by distance, paths of equal distance by their number of nodes. Fewer than
+    /// ```k``` paths are returned if there are no more, and none if ```dest``` is not reachable from ```src```.
+    pub fn k_shortest_paths(&self, src: usize, dest: usize, k: usize) -> Vec<ShortestPath<W>>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let mut result: Vec<ShortestPath<W>> = Vec::with_capacity(k);
+        if k == 0 || src.max(dest) >= self.node_bound() {
+            return result;
+        }
+
+        let mut buffer = DijkstraBuffer::new();
+        let mut stop = StopAt(dest);
+        self.dijkstra_filtered(
+            src,
+            None,
+            DijkstraStrategy::default(),
+            &mut buffer,
+            &mut stop,
+            |_, _| true,
+        );
+
+        let sp = traverse_path(src, dest, &buffer.nodes);
+        if !sp.is_feasible() {
+            return result;
+        }
+        result.push(sp);
+
+        let mut candidates: Vec<ShortestPath<W>> = Vec::new();
+        let mut banned_nodes = vec![false; self.node_bound()];
+        let mut banned_arcs = Vec::new();
+
+        while result.len() < k {
+            let prev = result[result.len() - 1].path.clone();
+            let prev_dists = result[result.len() - 1].dists.clone();
+
+            for ii in 0..prev.len() - 1 {
+                let spur = prev[ii];
+                let root = &prev[..=ii];
+
+                // Remove the arcs leaving the root path which were already used by the found paths with
+                // the same root, as well as the nodes of the root path, so that the spur path is new
+                // and loopless.
+                banned_arcs.clear();
+                for sp in &result {
+                    if sp.path.len() > ii + 1 && sp.path[..=ii] == *root {
+                        banned_arcs.push((spur, sp.path[ii + 1]));
+                    }
+                }
+                for &node in &prev[..ii] {
+                    banned_nodes[node] = true;
+                }
+
+                self.dijkstra_filtered(
+                    spur,
+                    None,
+                    DijkstraStrategy::default(),
+                    &mut buffer,
+                    &mut stop,
+                    |from, to| !banned_nodes[to] && !banned_arcs.contains(&(from, to)),
+                );
+
+                for &node in &prev[..ii] {
+                    banned_nodes[node] = false;
+                }
+
+                let spur_path = traverse_path(spur, dest, &buffer.nodes);
+                if spur_path.is_feasible() {
+                    let mut path = root.to_vec();
+                    path.extend_from_slice(&spur_path.path[1..]);
+
+                    if !candidates.iter().any(|sp| sp.path == path) {
+                        let root_dist = prev_dists[ii];
+                        let mut dists = prev_dists[..=ii].to_vec();
+                        dists.extend(spur_path.dists[1..].iter().map(|d| root_dist + *d));
+
+                        candidates.push(ShortestPath {
+                            src,
+                            dest,
+                            dist: root_dist + spur_path.dist,
+                            path,
+                            dists,
+                            feasible: true,
+                        });
+                    }
+                }
+            }
+
+            // Take the best candidate, preferring fewer nodes among paths of equal distance.
+            let best = (0..candidates.len()).fold(None, |best: Option<usize>, ii| match best {
+                Some(jj)
+                    if candidates[jj].dist < candidates[ii].dist
+                        || (candidates[jj].dist == candidates[ii].dist
+                            && candidates[jj].path.len() <= candidates[ii].path.len()) =>
+                {
+                    Some(jj)
+                }
+                _ => Some(ii),
+            });
+
+            match best {
+                Some(ii) => result.push(candidates.swap_remove(ii)),
+                None => break,
+            }
+        }
+
+        result
+    }
+
+    /// Changes the weight of the edge between ```u``` and ```v``` to ```new_w``` and updates the result
+    /// of [`SimpleGraph::sssp_dijkstra_lazy`] accordingly, so that the graph and the result stay in sync.
+    ///
+    /// The weight is changed with [`SimpleGraph::set_edge_weight`]: an undirected edge is updated in both
+    /// directions, an arc only from ```u``` to ```v```. Nothing happens if there is no such edge.
+    ///
+    /// Only the nodes whose shortest path can change are processed. If the weight decreases, the nodes
+    /// reachable through the cheaper edge are relaxed again, starting from its end node. If the weight
+    /// of an edge in the shortest path tree increases, the subtree below the edge is invalidated and
+    /// solved again from its boundary, which requires one pass over all edges to find the candidate
+    /// predecessors. Increasing the weight of any other edge changes nothing.
+    ///
+    /// The result is only correct if ```lazy``` is the complete result of a search from its source node
+    /// on this graph, i.e. not one of [`SimpleGraph::sssp_dijkstra_within`], and all weights are
+    /// non-negative.
+    pub fn update_edge_lazy(
+        &mut self,
+        lazy: &mut LazyShortestPaths<W>,
+        u: usize,
+        v: usize,
+        new_w: W,
+    ) where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let src = lazy.src;
+        let nodes = &mut lazy.paths;
+
+        let mut arcs = Vec::with_capacity(2);
+        for &(a, b) in &[(u, v), (v, u)] {
+            if self.has_edge(a, b) && !arcs.contains(&(a, b)) {
+                arcs.push((a, b));
+            }
+        }
+
+        if arcs.is_empty() {
+            return;
+        }
+
+        self.set_edge_weight(u, v, new_w);
+        let reached = |nodes: &[DijNode<W>], ii: usize| ii == src || nodes[ii].feasible;
+
+        // Invalidate the subtrees below the tree edges which became more expensive.
+        let mut affected = vec![false; nodes.len()];
+        let mut stack = Vec::new();
+
+        for &(a, b) in &arcs {
+            if b != src
+                && nodes[b].feasible
+                && nodes[b].pred == a
+                && nodes[a].dist + new_w > nodes[b].dist
+            {
+                stack.push(b);
+            }
+        }
+
+        if !stack.is_empty() {
+            let mut children = vec![Vec::new(); nodes.len()];
+            for (ii, node) in nodes.iter().enumerate() {
+                if ii != src && node.feasible {
+                    children[node.pred].push(ii);
+                }
+            }
+
+            while let Some(node) = stack.pop() {
+                if !affected[node] {
+                    affected[node] = true;
+                    stack.extend_from_slice(&children[node]);
+                }
+            }
+
+            let unreached = DijNode::new();
+            for node in nodes.iter_mut().zip(&affected).filter(|(_, a)| **a) {
+                *node.0 = unreached.clone();
+            }
+        }
+
+        let mut pq = PairingHeap::<usize, (W, usize)>::new();
+        let relax = |pq: &mut PairingHeap<usize, (W, usize)>,
+                     nodes: &mut [DijNode<W>],
+                     from: usize,
+                     to: usize,
+                     w: W| {
+            let alt = nodes[from].dist + w;
+            if alt < nodes[to].dist {
+                let count = nodes[from].len + 1;
+                let dijnode = &mut nodes[to];
+                dijnode.dist = alt;
+                dijnode.pred = from;
+                dijnode.len = count;
+                dijnode.feasible = true;
+                dijnode.visited = true;
+                pq.insert(to, (alt, to));
+            }
+        };
+
+        // Seed the queue with the changed edges and the edges entering the invalidated subtrees.
+        for &(a, b) in &arcs {
+            if reached(nodes, a) && !affected[a] {
+                relax(&mut pq, nodes, a, b, new_w);
+            }
+        }
+
+        if affected.contains(&true) {
+            for (&node, nb) in &self.weights {
+                if affected[node] || !reached(nodes, node) {
+                    continue;
+                }
+
+                for (idx, w) in nb {
+                    if affected[*idx] {
+                        relax(&mut pq, nodes, node, *idx, *w);
+                    }
+                }
+            }
+        }
+
+        while let Some((node, (prio, _))) = pq.delete_min() {
+            if prio > nodes[node].dist {
+                // The node was improved again after this entry had been inserted.
+                continue;
+            }
+
+            for (idx, w) in self.neighbours(node) {
+                relax(&mut pq, nodes, node, idx, *w);
+            }
+        }
+    }
+
+    #[inline]
+    fn dijkstra(&self, src: usize) -> Vec<DijNode<W>>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let mut buffer = DijkstraBuffer::new();
+        self.dijkstra_with_buffer(
+            src,
+            None,
+            DijkstraStrategy::default(),
+            &mut buffer,
+            &mut NoopVisitor,
+        );
+        buffer.nodes
+    }
+
+    /// Runs Dijkstra's algorithm and stores the result in ```buffer.nodes```.
+    ///
+    /// If ```max_dist``` is given, the search stops once the next node to be settled is farther away from
+    /// the source node than ```max_dist```. All nodes that are not settled by then are marked infeasible.
+    ///
+    /// Nodes with the same distance are settled by ascending node index, so that the result doesn't
+    /// depend on the chosen strategy. See [`DijkstraStrategy`].
+    #[inline]
+    fn dijkstra_with_buffer<V>(
+        &self,
+        src: usize,
+        max_dist: Option<W>,
+        strategy: DijkstraStrategy,
+        buffer: &mut DijkstraBuffer<W>,
+        visitor: &mut V,
+    ) where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+        V: DijkstraVisitor<W> + ?Sized,
+    {
+        self.dijkstra_filtered(src, max_dist, strategy, buffer, visitor, |_, _| true);
+    }
+
+    /// Same as ```dijkstra_with_buffer```, but only follows the arcs ```(from, to)``` for which
+    /// ```allowed(from, to)``` returns ```true```.
+    #[inline]
+    fn dijkstra_filtered<V, F>(
+        &self,
+        src: usize,
+        max_dist: Option<W>,
+        strategy: DijkstraStrategy,
+        buffer: &mut DijkstraBuffer<W>,
+        visitor: &mut V,
+        allowed: F,
+    ) where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+        V: DijkstraVisitor<W> + ?Sized,
+        F: Fn(usize, usize) -> bool,
+    {
+        let DijkstraBuffer { pq, nodes, elmts } = buffer;
+        let n_nodes = self.node_bound();
+        assert!(src < n_nodes, "source node {} is not in the graph", src);
+
+        pq.drain();
+        elmts.clear();
+        elmts.resize(n_nodes, HeapElmt::default());
+        elmts[src] = pq.insert2(src, (W::zero(), src));
+
+        nodes.clear();
+        nodes.resize(n_nodes, DijNode::<W>::new());
+        nodes[src].dist = W::zero();
+
+        while let Some((node, (prio, _))) = pq.delete_min() {
+            elmts[node].none();
+
+            if nodes[node].visited {
+                // A node can be inserted several times, only its first occurrence is settled.
+                visitor.on_skip(node);
+                continue;
+            }
+
+            if let Some(max_dist) = max_dist {
+                if prio > max_dist {
+                    stop_dijkstra(pq, nodes);
+                    break;
+                }
+            }
+
+            nodes[node].visited = true;
+            visitor.on_settle(node, prio);
+
+            if !visitor.on_pop(node, prio) {
+                // The visitor ended the search, this node is the last one to be settled.
+                stop_dijkstra(pq, nodes);
+                break;
+            }
+            let count = nodes[node].len + 1;
+
+            for (u, dist) in self.neighbours(node) {
+                if !allowed(node, u) {
+                    continue;
+                }
+
+                let dijnode = &mut nodes[u];
+                let alt = prio + *dist;
+                // The comparison is strict, so a path of equal distance, e.g. over a zero-weight edge, never
+                // adds another heap entry.
+                if !dijnode.visited && alt < dijnode.dist {
+                    dijnode.dist = alt;
+                    dijnode.pred = node;
+                    dijnode.len = count;
+                    dijnode.feasible = true;
+
+                    match strategy {
+                        DijkstraStrategy::DecreaseKey if !elmts[u].is_none() => {
+                            pq.update_prio(&elmts[u], (alt, u));
+                        }
+                        DijkstraStrategy::DecreaseKey => {
+                            elmts[u] = pq.insert2(u, (alt, u));
+                        }
+                        DijkstraStrategy::Reinsert => {
+                            pq.insert(u, (alt, u));
+                        }
+                    }
+
+                    visitor.on_relax(node, u, alt);
+                }
+            }
+        }
+    }
+
+    /// Checks that the graph is not empty and contains the given node.
+    fn check_node(&self, node: usize) -> Result<(), GraphError> {
+        if self.weights.is_empty() {
+            Err(GraphError::EmptyGraph)
+        } else if !self.has_node(node) {
+            Err(GraphError::NodeNotFound(node))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn insert_weight(&mut self, node1: usize, node2: usize, weight: W) {
+        self.sorted = false;
+
+        self.adjacency_mut(node1).push((node2, weight));
+    }
+
+    /// Replaces the weight of the first arc from ```node1``` to ```node2``` by ```resolve(existing weight,
+    /// weight)```, or inserts the arc if it doesn't exist. Returns whether the arc was inserted.
+    fn upsert_weight<F>(&mut self, node1: usize, node2: usize, weight: W, resolve: F) -> bool
+    where
+        W: Copy,
+        F: FnOnce(W, W) -> W,
+    {
+        match self.edge_weight_mut(node1, node2) {
+            Some(w) => {
+                *w = resolve(*w, weight);
+                false
+            }
+            None => {
+                self.insert_weight(node1, node2, weight);
+                true
+            }
+        }
+    }
+
+    /// Same as [`SimpleGraph::edge_weight`], but returns a mutable reference to the weight.
+    fn edge_weight_mut(&mut self, node1: usize, node2: usize) -> Option<&mut W> {
+        let sorted = self.sorted;
+        let nb = self.weights.get_mut(&node1)?;
+
+        if sorted {
+            let pos = nb.partition_point(|(idx, _)| *idx < node2);
+            nb.get_mut(pos)
+                .filter(|(idx, _)| *idx == node2)
+                .map(|(_, w)| w)
+        } else {
+            nb.iter_mut().find(|(idx, _)| *idx == node2).map(|(_, w)| w)
+        }
+    }
+
+    /// Returns the adjacency list of a node, adding the node with a list sized by the degree hint if it
+    /// doesn't exist yet.
+    #[inline]
+    fn adjacency_mut(&mut self, node: usize) -> &mut Vec<(usize, W)> {
+        let degree_hint = self.degree_hint;
+        self.weights
+            .entry(node)
+            .or_insert_with(|| Vec::with_capacity(degree_hint))
+    }
+
+    /// Exports the adjacency of the graph in Compressed Sparse Row (CSR) format.
+    ///
+    /// The function returns a tuple ```(indptr, indices, data)```, in which the neighbours of node ```i```
+    /// and the weights of the corresponding edges are stored in ```indices[indptr[i]..indptr[i + 1]]```
+    /// and ```data[indptr[i]..indptr[i + 1]]```. Nodes are indexed densely from ```0``` to the highest
+    /// node index in the graph, so that nodes without any edge have an empty row.
+    pub fn to_csr(&self) -> (Vec<usize>, Vec<usize>, Vec<W>)
+    where
+        W: Clone,
+    {
+        let n_rows = self.node_bound();
+        let n_entries = self.weights.values().map(|nb| nb.len()).sum();
+
+        let mut indptr = Vec::with_capacity(n_rows + 1);
+        let mut indices = Vec::with_capacity(n_entries);
+        let mut data = Vec::with_capacity(n_entries);
+
+        indptr.push(0);
+        for node in 0..n_rows {
+            for (idx, w) in self.neighbours(node) {
+                indices.push(idx);
+                data.push(w.clone());
+            }
+            indptr.push(indices.len());
+        }
+
+        (indptr, indices, data)
+    }
+
+    /// Write graph as a list of edges.
+    ///
+    /// Each line contains one edge, following [networkx](https://networkx.org/)'s format:
+    /// ```index 1 index 2 {'weight': {}}```. Payloads are not written.
+    pub fn write_edgelist<P>(&self, filepath: P) -> std::io::Result<()>
+    where
+        P: AsRef<Path>,
+        W: std::fmt::Display,
+    {
+        let file = File::create(filepath)?;
+        let mut file = LineWriter::new(file);
+
+        for (node_idx, nb) in &self.weights {
+            for (vtx_idx, w) in nb {
+                file.write_all(
+                    format!("{} {} {{'weight': {}}}\n", node_idx, vtx_idx, w).as_bytes(),
+                )?;
+            }
+        }
+
+        file.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Returns the key of the payload of the edge between two nodes, which is the same in both directions.
+#[inline(always)]
+fn edge_key(node1: usize, node2: usize) -> (usize, usize) {
+    (node1.min(node2), node1.max(node2))
+}
+
+/// Panics with a clear message if the weight of an edge is not comparable to itself, e.g. ```NaN```.
+#[inline(always)]
+fn assert_comparable<W>(node1: usize, node2: usize, weight: &W)
+where
+    W: PartialOrd,
+{
+    assert!(
+        weight.partial_cmp(weight).is_some(),
+        "the weight of the edge between {} and {} is not comparable, e.g. NaN",
+        node1,
+        node2
+    );
+}
+
+/// Returns the node index of the cell in row ```row``` and column ```col``` of a grid with ```width```
+/// columns, as used by [`SimpleGraph::from_grid`] and [`generate::grid_2d`].
+#[inline]
+pub fn grid_index(row: usize, col: usize, width: usize) -> usize {
+    row * width + col
+}
+
+/// Parses one line of a networkx edge list, e.g. ```0 1 {'weight': 7}```.
+fn parse_edgelist_line<W>(line: &str) -> Option<(usize, usize, W)>
+where
+    W: FromStr,
+{
+    let (nodes, attrs) = line.split_at(line.find('{')?);
+    let mut nodes = nodes.split_whitespace();
+    let node1 = nodes.next()?.parse().ok()?;
+    let node2 = nodes.next()?.parse().ok()?;
+
+    if nodes.next().is_some() {
+        return None;
+    }
+
+    let attrs = attrs.trim();
+    let attrs = attrs.strip_prefix('{')?.strip_suffix('}')?;
+
+    attrs
+        .split(',')
+        .find_map(|attr| {
+            let mut kv = attr.splitn(2, ':');
+            let key = kv.next()?.trim().trim_matches(|c| c == '\'' || c == '"');
+
+            if key == "weight" {
+                kv.next()?.trim().parse().ok()
+            } else {
+                None
+            }
+        })
+        .map(|weight| (node1, node2, weight))
+}
+
+/// Returns the largest eccentricity, or ```None``` if there is none or any of them is undefined.
+fn max_eccentricity<W, I>(eccs: I) -> Option<W>
+where
+    W: PartialOrd + Copy,
+    I: IntoIterator<Item = Option<W>>,
+{
+    let mut max = None;
+
+    for ecc in eccs {
+        let ecc = ecc?;
+        match max {
+            Some(m) if ecc <= m => {}
+            _ => max = Some(ecc),
+        }
+    }
+
+    max
+}
+
+/// Returns the smallest defined eccentricity, or ```None``` if there is none.
+fn min_eccentricity<W, I>(eccs: I) -> Option<W>
+where
+    W: PartialOrd + Copy,
+    I: IntoIterator<Item = Option<W>>,
+{
+    let mut min = None;
+
+    for ecc in eccs.into_iter().flatten() {
+        match min {
+            Some(m) if ecc >= m => {}
+            _ => min = Some(ecc),
+        }
+    }
+
+    min
+}
+
+impl<W> FromIterator<(usize, usize, W)> for SimpleGraph<W>
+where
+    W: Copy + PartialOrd,
+{
+    fn from_iter<I: IntoIterator<Item = (usize, usize, W)>>(iter: I) -> Self {
+        Self::from_edges(iter)
+    }
+}
+
+impl<W, E> Extend<(usize, usize, W)> for SimpleGraph<W, E>
+where
+    W: Copy + PartialOrd,
+{
+    fn extend<I: IntoIterator<Item = (usize, usize, W)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        // An edge can introduce up to two new nodes, but a connected graph has at most one node more than
+        // it has edges. So the number of edges is a fair estimate of the number of nodes of sparse graphs.
+        self.weights.reserve(iter.size_hint().0);
+
+        for (node1, node2, weight) in iter {
+            self.add_weighted_edges(node1, node2, weight);
+        }
+    }
+}
+
+/// Basic statistics of a [`SimpleGraph`], returned by [`SimpleGraph::stats`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphStats {
+    n_nodes: usize,
+    n_edges: usize,
+    min_degree: usize,
+    max_degree: usize,
+    mean_degree: f64,
+    density: f64,
+    connected: bool,
+}
+
+impl GraphStats {
+    /// Returns the number of nodes in the graph.
+    pub fn n_nodes(&self) -> usize {
+        self.n_nodes
+    }
+
+    /// Returns the number of edges in the graph.
+    pub fn n_edges(&self) -> usize {
+        self.n_edges
+    }
+
+    /// Returns the minimum degree over all nodes in the graph.
+    pub fn min_degree(&self) -> usize {
+        self.min_degree
+    }
+
+    /// Returns the maximum degree over all nodes in the graph.
+    pub fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    /// Returns the average degree over all nodes in the graph.
+    pub fn mean_degree(&self) -> f64 {
+        self.mean_degree
+    }
+
+    /// Returns the density of the graph, i.e. the ratio of the number of adjacency entries to the number
+    /// of possible ones, ```n_nodes * (n_nodes - 1)```.
+    pub fn density(&self) -> f64 {
+        self.density
+    }
+
+    /// Returns whether the graph is connected.
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+}
+
+/// Callbacks invoked while Dijkstra's algorithm runs, see [`SimpleGraph::sssp_dijkstra_visit`].
+///
+/// All methods do nothing by default, so that an implementation only needs to override the events
+/// it is interested in.
+pub trait DijkstraVisitor<W> {
+    /// Called when the shortest distance ```dist``` of a node from the source node is final.
+    fn on_settle(&mut self, node: usize, dist: W) {
+        let _ = (node, dist);
+    }
+
+    /// Called right after [`DijkstraVisitor::on_settle`], before the neighbours of the node are relaxed.
+    ///
+    /// Returning ```false``` ends the search early. The node itself is settled, while all nodes that are not
+    /// settled yet are marked infeasible. Returns ```true``` by default.
+    fn on_pop(&mut self, node: usize, dist: W) -> bool {
+        let _ = (node, dist);
+        true
+    }
+
+    /// Called when a shorter path to node ```to``` via node ```from``` with distance ```new_dist``` is found.
+    fn on_relax(&mut self, from: usize, to: usize, new_dist: W) {
+        let _ = (from, to, new_dist);
+    }
+
+    /// Called when an outdated entry of an already settled node is removed from the priority queue.
+    ///
+    /// This only happens with [`DijkstraStrategy::Reinsert`], see
+    /// [`SimpleGraph::sssp_dijkstra_visit_with_strategy`].
+    fn on_skip(&mut self, node: usize) {
+        let _ = node;
+    }
+}
+
+/// Callbacks invoked while Prim's algorithm runs, see [`mst_prim_visit`].
+pub trait PrimVisitor<W> {
+    /// Called when the edge between ```parent```, which is already part of the tree, and ```child``` with
+    /// the given weight joins the tree.
+    fn on_edge_added(&mut self, parent: usize, child: usize, weight: W) {
+        let _ = (parent, child, weight);
+    }
+}
+
+/// The way Dijkstra's algorithm updates the priority queue when a shorter path to a node is found.
+///
+/// Both strategies return identical results: nodes with the same distance leave the queue by ascending
+/// node index, so that they settle the nodes in the same order and choose the same predecessors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DijkstraStrategy {
+    /// Keeps a handle to each node in the priority queue and decreases its priority in place. The
+    /// queue never holds more than one entry per node. This is the default.
+    #[default]
+    DecreaseKey,
+    /// Inserts the node again with its new priority and skips the outdated entries once they are
+    /// removed from the queue.
+    Reinsert,
+}
+
+/// A visitor that ignores all events.
+struct NoopVisitor;
+
+impl<W> DijkstraVisitor<W> for NoopVisitor {}
+
+impl<W> PrimVisitor<W> for NoopVisitor {}
+
+/// A visitor that collects the edges of a minimum spanning tree and their payloads into a graph.
+struct MstBuilder<'a, W, E> {
+    graph: &'a SimpleGraph<W, E>,
+    tree: SimpleGraph<W, E>,
+}
+
+impl<W: Copy + PartialOrd, E: Clone> PrimVisitor<W> for MstBuilder<'_, W, E> {
+    fn on_edge_added(&mut self, parent: usize, child: usize, weight: W) {
+        match self.graph.edge_data(parent, child) {
+            Some(data) => self
+                .tree
+                .add_weighted_edge_with(parent, child, weight, data.clone()),
+            None => self.tree.add_weighted_edges(parent, child, weight),
+        }
+    }
+}
+
+/// A visitor that ends the search once a node is settled.
+struct StopAt(usize);
+
+impl<W> DijkstraVisitor<W> for StopAt {
+    fn on_pop(&mut self, node: usize, _dist: W) -> bool {
+        node != self.0
+    }
+}
+
+/// A visitor that ends the search once any of the target nodes is settled and remembers it.
+struct StopAtAny {
+    targets: Vec<bool>,
+    reached: Option<usize>,
+}
+
+impl<W> DijkstraVisitor<W> for StopAtAny {
+    fn on_pop(&mut self, node: usize, _dist: W) -> bool {
+        if self.targets.get(node).copied().unwrap_or(false) {
+            self.reached = Some(node);
+            false
+        } else {
+            true
+        }
+    }
+}
+
+/// A visitor that records the order in which nodes are settled.
+struct SettleOrder(Vec<usize>);
+
+impl<W> DijkstraVisitor<W> for SettleOrder {
+    fn on_settle(&mut self, node: usize, _dist: W) {
+        self.0.push(node);
+    }
+}
+
+/// Reusable memory for repeated runs of Dijkstra's algorithm.
+///
+/// See [`SimpleGraph::sssp_dijkstra_with_buffer`].
+#[derive(Debug, Default)]
+pub struct DijkstraBuffer<W> {
+    pq: PairingHeap<usize, (W, usize)>,
+    nodes: Vec<DijNode<W>>,
+    elmts: Vec<HeapElmt<usize, (W, usize)>>,
+}
+
+impl<W> DijkstraBuffer<W> {
+    /// Creates an empty buffer.
+    pub fn new() -> Self {
+        Self {
+            pq: PairingHeap::new(),
+            nodes: Vec::new(),
+            elmts: Vec::new(),
         }
     }
 
-    #[inline]
-    fn dijkstra(&self, src: usize) -> Vec<DijNode<W>>
-    where
-        W: Bounded + Num + Zero + PartialOrd + Copy,
-    {
-        let mut pq = PairingHeap::<usize, W>::new();
-        pq.insert(src, W::zero());
-
-        let mut nodes = vec![DijNode::<W>::new(); self.weights.len()];
-        nodes[src].dist = W::zero();
-        let mut len = pq.len();
+    /// Creates an empty buffer with enough capacity for a graph with ```n_nodes``` nodes.
+    pub fn with_capacity(n_nodes: usize) -> Self {
+        Self {
+            pq: PairingHeap::new(),
+            nodes: Vec::with_capacity(n_nodes),
+            elmts: Vec::with_capacity(n_nodes),
+        }
+    }
+}
 
-        while len != 0 {
-            let (node, prio) = pq.delete_min().unwrap();
-            let count = nodes[node].len + 1;
+/// The error returned by the checked graph functions, such as [`SimpleGraph::try_sssp_dijkstra`],
+/// [`SimpleGraph::add_weighted_edges_checked`] and [`try_mst_prim`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphError {
+    /// The graph has no nodes.
+    EmptyGraph,
+    /// The node with the given index doesn't exist in the graph.
+    NodeNotFound(usize),
+    /// An edge would connect the node with the given index to itself.
+    SelfLoop(usize),
+    /// The weight of the edge between the two given nodes is not comparable to itself, e.g. ```NaN```.
+    InvalidWeight(usize, usize),
+}
 
-            if let Some(nb) = self.neighbours(&node) {
-                for (u, dist) in nb {
-                    let dijnode = &mut nodes[*u];
-                    let alt = prio + *dist;
-                    if !dijnode.visited && alt < dijnode.dist {
-                        dijnode.dist = alt;
-                        dijnode.pred = node;
-                        dijnode.len = count;
-                        dijnode.feasible = true;
-                        pq.insert(*u, alt);
-                    }
-                }
+impl std::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::EmptyGraph => write!(f, "the graph is empty"),
+            GraphError::NodeNotFound(node) => write!(f, "node {} is not in the graph", node),
+            GraphError::SelfLoop(node) => write!(f, "edge from node {} to itself", node),
+            GraphError::InvalidWeight(node1, node2) => {
+                write!(f, "invalid weight of edge ({}, {})", node1, node2)
             }
-
-            let dijnode = nodes.get_mut(node).unwrap();
-            dijnode.visited = true;
-            len = pq.len();
         }
+    }
+}
 
-        nodes
+impl std::error::Error for GraphError {}
+
+/// The error returned when an algorithm requires a directed acyclic graph, but the graph contains a cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotADag {
+    node: usize,
+}
+
+impl NotADag {
+    /// Returns a node that lies on a cycle.
+    pub fn node(&self) -> usize {
+        self.node
     }
+}
 
-    fn insert_weight(&mut self, node1: usize, node2: usize, weight: W) {
-        match self.weights.get_mut(&node1) {
-            Some(v) => {
-                v.push((node2, weight));
-            }
-            None => {
-                let v = vec![(node2, weight)];
-                self.weights.insert(node1, v);
-            }
-        }
+impl std::fmt::Display for NotADag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the graph contains a cycle through node {}", self.node)
     }
+}
 
-    /// Write graph as a list of edges.
-    ///
-    /// Each line contains one edge, following [networkx](https://networkx.org/)'s format:
-    /// ```index 1 index 2 {'weight': {}}```.
-    pub fn write_edgelist<P>(&self, filepath: P) -> std::io::Result<()>
-    where
-        P: AsRef<Path>,
-        W: std::fmt::Display,
-    {
-        let file = File::create(filepath)?;
-        let mut file = LineWriter::new(file);
+impl std::error::Error for NotADag {}
 
-        for (node_idx, nb) in &self.weights {
-            for (vtx_idx, w) in nb {
-                file.write_all(
-                    format!("{} {} {{'weight': {}}}\n", node_idx, vtx_idx, w).as_bytes(),
-                )?;
-            }
-        }
+/// The error returned when a graph contains a cycle of negative total weight, see
+/// [`SimpleGraph::sssp_bellman_ford`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegativeCycle {
+    cycle: Vec<usize>,
+}
 
-        file.flush()?;
+impl NegativeCycle {
+    /// Returns the nodes of the cycle in the direction of its edges. The edge from the last node back to
+    /// the first one closes the cycle.
+    pub fn cycle(&self) -> &[usize] {
+        &self.cycle
+    }
+}
 
-        Ok(())
+impl std::fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the graph contains a negative cycle: {:?}", self.cycle)
     }
 }
 
+impl std::error::Error for NegativeCycle {}
+
 /// The shortest path from a source node to a destination node.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShortestPath<W> {
     src: usize,
     dest: usize,
     feasible: bool,
     dist: W,
     path: Vec<usize>,
+    /// The distance of each node of the path from the source node.
+    dists: Vec<W>,
 }
 
 impl<W> ShortestPath<W> {
+    /// Creates the result for a destination node that is not reachable from the source node.
+    fn infeasible(src: usize, dest: usize) -> Self
+    where
+        W: Zero,
+    {
+        ShortestPath {
+            src,
+            dest,
+            dist: W::zero(),
+            path: Vec::with_capacity(0),
+            dists: Vec::with_capacity(0),
+            feasible: false,
+        }
+    }
+
     /// Returns the index of the source node in the shortest path.
     pub fn src(&self) -> usize {
         self.src
@@ -253,20 +2832,98 @@ impl<W> ShortestPath<W> {
     }
 
     /// Returns whether a path from the source node to the destination node is feasible.
+    ///
+    /// For floating point weights, a feasible path always has a finite distance.
     pub fn is_feasible(&self) -> bool {
         self.feasible
     }
 
     /// Returns the path from the source node to destination node.
     ///
-    /// The first element of the vector is the source node, the last the destination node.
-    pub fn path(&self) -> &Vec<usize> {
+    /// The first element is the source node, the last the destination node.
+    pub fn path(&self) -> &[usize] {
         &self.path
     }
+
+    /// Consumes the shortest path and returns its nodes, without copying them.
+    pub fn into_path(self) -> Vec<usize> {
+        self.path
+    }
+
+    /// Returns the number of nodes on the path, or ```0``` if the path is not feasible.
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Checks whether the path has no nodes, which is the case if it is not feasible.
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// Returns the number of edges on the path, or ```0``` if the path is not feasible.
+    pub fn hops(&self) -> usize {
+        self.path.len().saturating_sub(1)
+    }
+
+    /// Returns the edges of the path as tuples ```(node 1, node 2, weight)```, from the source node to the
+    /// destination node. The vector is empty if the path is not feasible.
+    ///
+    /// The weights are derived from the distances computed by the shortest path algorithm, so that the
+    /// graph is not queried again.
+    pub fn segments(&self) -> Vec<(usize, usize, W)>
+    where
+        W: Sub<Output = W> + Copy,
+    {
+        self.path
+            .windows(2)
+            .zip(self.dists.windows(2))
+            .map(|(nodes, dists)| (nodes[0], nodes[1], dists[1] - dists[0]))
+            .collect()
+    }
+
+    /// Returns the payloads of the edges along the path, in the order in which they are traversed.
+    ///
+    /// The payloads are looked up in the graph in which the path was found, see
+    /// [`SimpleGraph::add_weighted_edge_with`]. An edge without a payload yields ```None```.
+    pub fn edge_data<'g, E>(&self, graph: &'g SimpleGraph<W, E>) -> Vec<Option<&'g E>> {
+        self.path
+            .windows(2)
+            .map(|nodes| graph.edge_data(nodes[0], nodes[1]))
+            .collect()
+    }
+}
+
+impl<W> Index<usize> for ShortestPath<W> {
+    type Output = usize;
+
+    /// Returns the node at the given position of the path.
+    fn index(&self, index: usize) -> &usize {
+        &self.path[index]
+    }
+}
+
+impl<W> IntoIterator for ShortestPath<W> {
+    type Item = usize;
+    type IntoIter = std::vec::IntoIter<usize>;
+
+    /// Returns an iterator over the nodes of the path, from the source node to the destination node.
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.into_iter()
+    }
+}
+
+impl<'a, W> IntoIterator for &'a ShortestPath<W> {
+    type Item = &'a usize;
+    type IntoIter = std::slice::Iter<'a, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.path.iter()
+    }
 }
 
 /// A struct representing the intermediate output of Dijkstra's algorithm.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LazyShortestPaths<W> {
     src: usize,
     paths: Vec<DijNode<W>>,
@@ -274,6 +2931,9 @@ pub struct LazyShortestPaths<W> {
 
 impl<W> LazyShortestPaths<W> {
     /// Returns the shortest path for a given node.
+    ///
+    /// If the node is not reachable from the source node, including node indices that are out of range,
+    /// the returned path is infeasible. Use [`LazyShortestPaths::try_get`] to tell both cases apart.
     pub fn get(&self, node_index: usize) -> ShortestPath<W>
     where
         W: Zero + Copy,
@@ -281,64 +2941,119 @@ impl<W> LazyShortestPaths<W> {
         traverse_path(self.src, node_index, &self.paths)
     }
 
-    /// Returns the shortest paths for all nodes.
-    pub fn get_all(&self) -> Vec<ShortestPath<W>>
+    /// Returns the shortest path for a given node, or ```None``` if the node index is out of range.
+    pub fn try_get(&self, node_index: usize) -> Option<ShortestPath<W>>
     where
         W: Zero + Copy,
     {
-        let n_nodes = self.paths.len();
-        let mut result: Vec<ShortestPath<W>> = Vec::with_capacity(n_nodes);
+        if node_index < self.paths.len() {
+            Some(traverse_path(self.src, node_index, &self.paths))
+        } else {
+            None
+        }
+    }
 
-        for ii in 0..n_nodes {
-            let end_node = &self.paths[ii];
-            let expected = end_node.len + 1;
+    /// Returns the distance of a given node from the source node, or ```None``` if the node is not
+    /// reachable or its index is out of range.
+    ///
+    /// Unlike [`LazyShortestPaths::get`], the path itself is not built.
+    pub fn dist(&self, node_index: usize) -> Option<W>
+    where
+        W: Copy,
+    {
+        self.node(node_index).map(|node| node.dist)
+    }
 
-            let sp = if end_node.feasible {
-                let mut len = 0;
-                let mut chain = Vec::with_capacity(expected);
-                let mut next = end_node.pred;
+    /// Returns the node preceding a given node on its shortest path from the source node, or ```None```
+    /// if the node is the source node, not reachable or its index is out of range.
+    pub fn pred(&self, node_index: usize) -> Option<usize> {
+        match self.node(node_index) {
+            Some(node) if node_index != self.src => Some(node.pred),
+            _ => None,
+        }
+    }
 
-                while len < expected {
-                    if next < ii {
-                        let mut sp = result[next].path.clone();
-                        if ii < self.src {
-                            sp.reverse();
-                        }
+    /// Checks whether a given node is reachable from the source node. The source node itself is
+    /// always reachable.
+    pub fn is_reachable(&self, node_index: usize) -> bool {
+        self.node(node_index).is_some()
+    }
 
-                        sp.append(&mut chain);
-                        chain = sp;
-                        break;
-                    }
+    /// Returns the distance from the source node to every node, indexed by node, or ```None``` for nodes
+    /// that are not reachable.
+    ///
+    /// Unlike [`LazyShortestPaths::get_all`], no path is reconstructed.
+    pub fn distances(&self) -> Vec<Option<W>>
+    where
+        W: Copy,
+    {
+        (0..self.paths.len()).map(|ii| self.dist(ii)).collect()
+    }
 
-                    chain.insert(0, next);
-                    next = self.paths[next].pred;
-                    len = chain.len();
-                }
+    /// Returns the state of a node if it is the source node or reachable from it.
+    fn node(&self, node_index: usize) -> Option<&DijNode<W>> {
+        self.paths
+            .get(node_index)
+            .filter(|node| node.feasible || node_index == self.src)
+    }
 
-                ShortestPath {
-                    src: self.src,
-                    dest: ii,
-                    dist: end_node.dist,
-                    path: chain,
-                    feasible: true,
-                }
-            } else {
-                ShortestPath {
-                    src: self.src,
-                    dest: ii,
-                    dist: <W as Zero>::zero(),
-                    path: Vec::with_capacity(0),
-                    feasible: false,
-                }
-            };
+    /// Returns the shortest paths for all nodes.
+    pub fn get_all(&self) -> Vec<ShortestPath<W>>
+    where
+        W: Zero + Copy,
+    {
+        (0..self.paths.len())
+            .map(|ii| traverse_path(self.src, ii, &self.paths))
+            .collect()
+    }
 
-            result.push(sp);
-        }
+    /// Same as [`LazyShortestPaths::get_all`], but builds the paths in parallel.
+    ///
+    /// The paths are returned in the same order as by [`LazyShortestPaths::get_all`].
+    #[cfg(feature = "rayon")]
+    pub fn get_all_par(&self) -> Vec<ShortestPath<W>>
+    where
+        W: Zero + Copy + Send + Sync,
+    {
+        use rayon::prelude::*;
 
-        result
+        (0..self.paths.len())
+            .into_par_iter()
+            .map(|ii| traverse_path(self.src, ii, &self.paths))
+            .collect()
+    }
+
+    /// Returns an iterator over all nodes whose shortest path from the source node is known, including
+    /// the source node itself.
+    pub fn settled_nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.paths
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.visited)
+            .map(|(ii, _)| ii)
+    }
+
+    /// Returns the predecessor of every node in the shortest path tree.
+    ///
+    /// The element at index ```i``` is the node preceding node ```i``` on its shortest path from the source
+    /// node, or ```None``` if node ```i``` is the source node or not reachable from it.
+    pub fn predecessors(&self) -> Vec<Option<usize>> {
+        self.paths
+            .iter()
+            .enumerate()
+            .map(|(ii, node)| {
+                if node.feasible && ii != self.src {
+                    Some(node.pred)
+                } else {
+                    None
+                }
+            })
+            .collect()
     }
 
     /// Returns the shortest paths for a given list of node indices.
+    ///
+    /// As with [`LazyShortestPaths::get`], the paths to unreachable or out-of-range nodes are infeasible.
     pub fn get_list(&self, node_indices: &[usize]) -> Vec<ShortestPath<W>>
     where
         W: Zero + Copy,
@@ -354,6 +3069,7 @@ impl<W> LazyShortestPaths<W> {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct DijNode<W> {
     /// Id of the predecessor's node in SSSP solution from Dijkstra's algorithm.
     pred: usize,
@@ -370,11 +3086,11 @@ struct DijNode<W> {
 impl<W> DijNode<W> {
     pub fn new() -> Self
     where
-        W: Bounded,
+        W: Infinity + Num,
     {
         Self {
             pred: 0,
-            dist: <W as Bounded>::max_value(),
+            dist: W::infinity(),
             visited: false,
             len: 0,
             feasible: false,
@@ -383,46 +3099,59 @@ impl<W> DijNode<W> {
 }
 
 #[inline(always)]
+/// Empties the priority queue and marks all nodes that are not settled yet as infeasible.
+fn stop_dijkstra<W>(pq: &mut PairingHeap<usize, (W, usize)>, nodes: &mut [DijNode<W>]) {
+    pq.drain();
+    for dijnode in nodes.iter_mut().filter(|n| !n.visited) {
+        dijnode.feasible = false;
+    }
+}
+
 fn traverse_path<W>(src: usize, dest: usize, paths: &[DijNode<W>]) -> ShortestPath<W>
 where
     W: Zero + Copy,
 {
-    let end_node = &paths[dest];
-    if end_node.feasible {
-        let expected = end_node.len + 1;
+    // Node indices beyond the solved range are never reachable, while the source node always is, with
+    // a path of its own.
+    match paths.get(dest) {
+        Some(end_node) if end_node.feasible || dest == src => {
+            let expected = end_node.len + 1;
 
-        let mut len = 0;
-        let mut path = Vec::with_capacity(expected);
-        path.push(dest);
-        let mut next = end_node.pred;
+            let mut path = Vec::with_capacity(expected);
+            let mut dists = Vec::with_capacity(expected);
+            let mut next = dest;
 
-        while len < expected {
-            path.insert(0, next);
-            next = paths[next].pred;
-            len = path.len();
-        }
+            while path.len() < expected {
+                path.push(next);
+                dists.push(paths[next].dist);
+                next = paths[next].pred;
+            }
 
-        ShortestPath {
-            src,
-            dest,
-            dist: end_node.dist,
-            path,
-            feasible: true,
-        }
-    } else {
-        ShortestPath {
-            src,
-            dest,
-            dist: <W as Zero>::zero(),
-            path: Vec::with_capacity(0),
-            feasible: false,
+            path.reverse();
+            dists.reverse();
+
+            ShortestPath {
+                src,
+                dest,
+                dist: end_node.dist,
+                path,
+                dists,
+                feasible: true,
+            }
         }
+        _ => ShortestPath::infeasible(src, dest),
     }
 }
 
 /// Find the minimum spanning tree (MST) in a graph using Prim's algorithm.
 ///
-/// The function returns a tuple of the total weight of the MST and a simple graph, whose edges are the MST's edges.
+/// The function returns a tuple of the total weight of the MST and a simple graph, whose edges are the MST's edges
+/// with their payloads.
+/// If the graph is not connected, the result spans every component, i.e. it is a minimum spanning forest. Use
+/// [`mst_forest`] to get one tree per component instead.
+///
+/// Ties between equal weights are broken by node index, so that the result only depends on the edges of the
+/// graph and not on the order in which they were added.
 ///
 /// # Examples
 /// ```rust
@@ -453,62 +3182,261 @@ where
 /// assert_eq!(g0.n_nodes(), g4.n_nodes());
 /// assert_eq!(g0.n_edges(), g4.n_edges());
 /// ```
-pub fn mst_prim<W>(graph: &SimpleGraph<W>, src: usize) -> (SimpleGraph<W>, W)
+pub fn mst_prim<W, E>(graph: &SimpleGraph<W, E>, src: usize) -> (SimpleGraph<W, E>, W)
+where
+    W: Copy + PartialOrd + Bounded + Zero + AddAssign,
+    E: Clone,
+{
+    let mut builder = MstBuilder {
+        graph,
+        tree: SimpleGraph::with_edge_data(),
+    };
+    builder.tree.reserve_nodes(graph.n_nodes());
+    let dist = mst_prim_visit(graph, src, &mut builder);
+
+    (builder.tree, dist)
+}
+
+/// Same as [`mst_prim`], but returns [`GraphError::EmptyGraph`] if the graph has no nodes and
+/// [`GraphError::NodeNotFound`] if the source node doesn't exist in the graph.
+pub fn try_mst_prim<W, E>(
+    graph: &SimpleGraph<W, E>,
+    src: usize,
+) -> Result<(SimpleGraph<W, E>, W), GraphError>
+where
+    W: Copy + PartialOrd + Bounded + Zero + AddAssign,
+    E: Clone,
+{
+    graph.check_node(src)?;
+    Ok(mst_prim(graph, src))
+}
+
+/// Find the minimum spanning tree (MST) in a graph using Prim's algorithm and reports each edge to a visitor
+/// as soon as it joins the tree.
+///
+/// Returns the total weight of the MST. Like [`mst_prim`], the result spans every component of a disconnected
+/// graph.
+///
+/// # Examples
+/// ```rust
+/// use pheap::graph::{mst_prim_visit, PrimVisitor, SimpleGraph};
+///
+/// struct Edges(Vec<(usize, usize, u32)>);
+///
+/// impl PrimVisitor<u32> for Edges {
+///     fn on_edge_added(&mut self, parent: usize, child: usize, weight: u32) {
+///         self.0.push((parent, child, weight));
+///     }
+/// }
+///
+/// let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 4), (0, 2, 1), (1, 2, 2)]);
+/// let mut edges = Edges(Vec::new());
+///
+/// assert_eq!(3, mst_prim_visit(&g, 0, &mut edges));
+/// assert_eq!(vec![(0, 2, 1), (2, 1, 2)], edges.0);
+/// ```
+pub fn mst_prim_visit<W, E, V>(graph: &SimpleGraph<W, E>, src: usize, visitor: &mut V) -> W
+where
+    W: Copy + PartialOrd + Bounded + Zero + AddAssign,
+    V: PrimVisitor<W> + ?Sized,
+{
+    let mut dist = <W as Zero>::zero();
+    for node in prim(graph, src, visitor) {
+        if node.parent.is_some() {
+            dist += node.dist;
+        }
+    }
+
+    dist
+}
+
+/// Find the minimum spanning tree (MST) in a graph using Prim's algorithm and returns its edges.
+///
+/// Unlike [`mst_prim`], this function doesn't build a new graph. Instead, it returns a tuple of the list of
+/// the MST's edges, each given as ```(parent node, child node, weight)```, and the total weight of the MST.
+/// The edges are sorted by the index of the child node.
+///
+/// # Examples
+/// ```rust
+/// use pheap::graph::{mst_prim_edges, SimpleGraph};
+///
+/// let mut g = SimpleGraph::<u32>::new();
+///
+/// g.add_weighted_edges(0, 1, 4);
+/// g.add_weighted_edges(0, 2, 1);
+/// g.add_weighted_edges(1, 2, 2);
+///
+/// let (edges, dist) = mst_prim_edges(&g, 0);
+///
+/// assert_eq!(3, dist);
+/// assert_eq!(vec![(2, 1, 2), (0, 2, 1)], edges);
+/// ```
+pub fn mst_prim_edges<W, E>(graph: &SimpleGraph<W, E>, src: usize) -> (Vec<(usize, usize, W)>, W)
+where
+    W: Copy + PartialOrd + Bounded + Zero + AddAssign,
+{
+    let nodes = prim(graph, src, &mut NoopVisitor);
+    let mut edges = Vec::with_capacity(nodes.len().saturating_sub(1));
+    let mut dist = <W as Zero>::zero();
+    for node in nodes {
+        if let Some(p) = node.parent {
+            edges.push((p, node.idx, node.dist));
+            dist += node.dist;
+        }
+    }
+
+    (edges, dist)
+}
+
+/// Finds a minimum spanning tree for every connected component of a graph using Prim's algorithm.
+///
+/// Each tree is returned as a simple graph, whose edges keep their payloads, together with its total weight. The trees are sorted by the
+/// smallest node index they contain. A node without edges forms a tree of its own with weight ```0```.
+///
+/// # Examples
+/// ```rust
+/// use pheap::graph::{mst_forest, SimpleGraph};
+///
+/// let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 4), (0, 2, 1), (1, 2, 2), (3, 4, 5)]);
+/// let forest = mst_forest(&g);
+///
+/// assert_eq!(2, forest.len());
+/// assert_eq!(3, forest[0].1);
+/// assert_eq!(5, forest[1].1);
+/// ```
+pub fn mst_forest<W, E>(graph: &SimpleGraph<W, E>) -> Vec<(SimpleGraph<W, E>, W)>
 where
     W: Copy + PartialOrd + Bounded + Zero + AddAssign,
+    E: Clone,
+{
+    let src = match graph.weights.keys().min() {
+        Some(src) => *src,
+        None => return Vec::new(),
+    };
+
+    // Prim's algorithm already spans all components, every node without parent is the root of a tree.
+    let nodes = prim(graph, src, &mut NoopVisitor);
+    let parents: HashMap<usize, Option<usize>> =
+        nodes.iter().map(|node| (node.idx, node.parent)).collect();
+
+    let mut forest: Vec<(SimpleGraph<W, E>, W)> = Vec::new();
+    let mut tree_of: HashMap<usize, usize> = HashMap::with_capacity(nodes.len());
+    let mut chain = Vec::new();
+
+    // The nodes are sorted by index, so that the trees are created in the order of their smallest node.
+    for node in &nodes {
+        let mut next = node.idx;
+        let tree = loop {
+            if let Some(tree) = tree_of.get(&next) {
+                break *tree;
+            }
+
+            chain.push(next);
+            match parents[&next] {
+                Some(parent) => next = parent,
+                None => {
+                    forest.push((SimpleGraph::with_edge_data(), W::zero()));
+                    break forest.len() - 1;
+                }
+            }
+        };
+
+        for ii in chain.drain(..) {
+            tree_of.insert(ii, tree);
+        }
+
+        let (g, dist) = &mut forest[tree];
+        match node.parent {
+            Some(parent) => {
+                match graph.edge_data(parent, node.idx) {
+                    Some(data) => {
+                        g.add_weighted_edge_with(parent, node.idx, node.dist, data.clone())
+                    }
+                    None => g.add_weighted_edges(parent, node.idx, node.dist),
+                }
+                *dist += node.dist;
+            }
+            None => g.add_node(node.idx),
+        }
+    }
+
+    forest
+}
+
+#[inline]
+fn prim<W, E, V>(graph: &SimpleGraph<W, E>, src: usize, visitor: &mut V) -> Vec<PrimNode<W>>
+where
+    W: Copy + PartialOrd + Bounded + Zero,
+    V: PrimVisitor<W> + ?Sized,
 {
-    let mut pq = PairingHeap::<usize, W>::new();
-    let mut nodes: Vec<_> = (0..graph.n_nodes())
-        .map(|ii| {
+    assert!(
+        graph.has_node(src),
+        "source node {} is not in the graph",
+        src
+    );
+
+    let mut ids: Vec<usize> = graph.weights.keys().copied().collect();
+    ids.sort_unstable();
+
+    // Sparse node ids, e.g. hashed ones, are mapped to dense slots, so that the table below doesn't
+    // grow with the largest id. Contiguous ids are used as slots directly.
+    let dense = ids.last() == Some(&(ids.len() - 1));
+    let slots: HashMap<usize, usize> = if dense {
+        HashMap::new()
+    } else {
+        ids.iter()
+            .enumerate()
+            .map(|(slot, id)| (*id, slot))
+            .collect()
+    };
+    let slot = |node: usize| if dense { node } else { slots[&node] };
+
+    // The nodes are inserted by index, so a stable heap breaks ties between equal weights by the smaller
+    // node index. Together with the strict comparison below, the tree then only depends on the edges of
+    // the graph, not on the order in which they were added.
+    let mut pq = StablePairingHeap::<usize, W>::new_stable();
+    let mut nodes: Vec<_> = ids
+        .iter()
+        .enumerate()
+        .map(|(ii, id)| {
             let mut node = PrimNode::<W>::new();
-            node.dist = if ii == src {
+            node.dist = if *id == src {
                 <W as Zero>::zero()
             } else {
                 <W as Bounded>::max_value()
             };
-            node.idx = ii;
+            node.idx = *id;
             node.heap = pq.insert2(ii, node.dist);
             node
         })
         .collect();
 
-    let mut len = pq.len();
-
-    while len != 0 {
-        let (node, _) = pq.delete_min().unwrap();
+    while let Some((node, _)) = pq.delete_min() {
         nodes[node].heap.none();
+        let id = nodes[node].idx;
 
-        if let Some(nb) = graph.neighbours(&node) {
-            for (u, dist) in nb {
-                let primnode = &mut nodes[*u];
-                if !primnode.heap.is_none() && *dist < primnode.dist {
-                    primnode.dist = *dist;
-                    primnode.parent = Some(node);
-                    pq.update_prio(&primnode.heap, primnode.dist);
-                }
-            }
+        if let Some(parent) = nodes[node].parent {
+            visitor.on_edge_added(parent, id, nodes[node].dist);
         }
 
-        len = pq.len();
-    }
-
-    let mut rg = SimpleGraph::<W>::with_capacity(graph.n_nodes());
-    let mut dist = <W as Zero>::zero();
-    for node in nodes {
-        if let Some(p) = node.parent {
-            rg.add_weighted_edges(p, node.idx, node.dist);
-            dist += node.dist;
+        for (u, dist) in graph.neighbours(id) {
+            let primnode = &mut nodes[slot(u)];
+            if !primnode.heap.is_none() && *dist < primnode.dist {
+                primnode.dist = *dist;
+                primnode.parent = Some(id);
+                pq.update_prio(&primnode.heap, primnode.dist);
+            }
         }
     }
 
-    (rg, dist)
+    nodes
 }
 
 #[derive(Clone, Debug)]
 struct PrimNode<W> {
     idx: usize,
     parent: Option<usize>,
-    heap: HeapElmt<usize, W>,
+    heap: HeapElmt<usize, Stamped<W>>,
     dist: W,
 }
 
@@ -520,7 +3448,7 @@ impl<W> PrimNode<W> {
         Self {
             idx: 0,
             parent: None,
-            heap: HeapElmt::<usize, W>::default(),
+            heap: HeapElmt::default(),
             dist: <W as Bounded>::max_value(),
         }
     }
@@ -0,0 +1,208 @@
+//! Goal-directed point-to-point search with landmarks (ALT: A*, landmarks and triangle inequality).
+//!
+//! The distances from and to a few landmark nodes are computed once with Dijkstra's algorithm. By the
+//! triangle inequality, they give a lower bound of the distance between any two nodes, which guides
+//! an A* search towards the destination node, so that far fewer nodes are settled than by a plain
+//! Dijkstra search.
+//!
+//! # Examples
+//! ```
+//! use pheap::graph::{alt::AltPreprocessed, SimpleGraph};
+//!
+//! let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7), (0, 2, 9), (1, 2, 10), (2, 3, 11)]);
+//! let alt = AltPreprocessed::build(&g, 2);
+//!
+//! let sp = alt.query(&g, 0, 3);
+//! assert_eq!(20, sp.dist());
+//! assert_eq!(&[0, 2, 3], sp.path());
+//! ```
+//!
+//! With the optional ```serde``` feature, the preprocessed landmark tables implement ```Serialize``` and
+//! ```Deserialize```, so that they can be stored and loaded together with the graph.
+
+use num_traits::{Num, Zero};
+
+use super::{traverse_path, DijNode, Infinity, ShortestPath, SimpleGraph};
+use crate::PairingHeap;
+
+/// The preprocessed landmark tables of a graph for ALT queries.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltPreprocessed<W> {
+    landmarks: Vec<usize>,
+    /// ```from[i][v]``` is the distance from landmark ```i``` to node ```v```.
+    from: Vec<Vec<Option<W>>>,
+    /// ```to[i][v]``` is the distance from node ```v``` to landmark ```i```.
+    to: Vec<Vec<Option<W>>>,
+}
+
+impl<W> AltPreprocessed<W> {
+    /// Selects up to ```n_landmarks``` landmarks and computes the distances from and to each of them.
+    ///
+    /// The landmarks are picked with the farthest-point heuristic: the first one is the node farthest
+    /// away from the node with the smallest index, each further one the node farthest away from all
+    /// landmarks picked so far. Nodes which no landmark reaches are preferred, so that every connected
+    /// component gets a landmark.
+    pub fn build<E>(graph: &SimpleGraph<W, E>, n_landmarks: usize) -> Self
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+        E: Clone,
+    {
+        let n_nodes = graph.node_bound();
+        let mut alt = Self {
+            landmarks: Vec::with_capacity(n_landmarks),
+            from: Vec::with_capacity(n_landmarks),
+            to: Vec::with_capacity(n_landmarks),
+        };
+
+        let start = match graph.weights.keys().min() {
+            Some(start) if n_landmarks > 0 => *start,
+            _ => return alt,
+        };
+
+        // Distances to a landmark are distances from it in the graph with all arcs reversed.
+        let reversed = graph.reverse();
+
+        let mut candidate = farthest(&distances(graph, start, n_nodes), &[]);
+        // The smallest distance of each node from any landmark picked so far.
+        let mut min_dist = vec![None; n_nodes];
+
+        while let Some(landmark) = candidate {
+            let from = distances(graph, landmark, n_nodes);
+            let to = distances(&reversed, landmark, n_nodes);
+
+            for (min, dist) in min_dist.iter_mut().zip(&from) {
+                *min = match (*min, *dist) {
+                    (Some(m), Some(d)) if d < m => Some(d),
+                    (None, d) => d,
+                    (m, _) => m,
+                };
+            }
+
+            alt.landmarks.push(landmark);
+            alt.from.push(from);
+            alt.to.push(to);
+
+            if alt.landmarks.len() == n_landmarks {
+                break;
+            }
+
+            candidate = graph
+                .weights
+                .keys()
+                .copied()
+                .filter(|node| min_dist[*node].is_none())
+                .min()
+                .or_else(|| farthest(&min_dist, &alt.landmarks));
+        }
+
+        alt
+    }
+
+    /// Returns the selected landmarks.
+    pub fn landmarks(&self) -> &[usize] {
+        &self.landmarks
+    }
+
+    /// Finds the shortest path from ```src``` to ```dest``` with an A* search guided by the landmarks.
+    ///
+    /// The graph must be the one the tables were built from. The result is the same as the one of
+    /// [`SimpleGraph::sssp_dijkstra`], except that another path of the same distance may be returned.
+    pub fn query<E>(&self, graph: &SimpleGraph<W, E>, src: usize, dest: usize) -> ShortestPath<W>
+    where
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let n_nodes = graph.node_bound().max(src + 1).max(dest + 1);
+        let mut nodes = vec![DijNode::<W>::new(); n_nodes];
+        nodes[src].dist = W::zero();
+
+        let mut pq = PairingHeap::<usize, (W, usize)>::new();
+        pq.insert(src, (self.lower_bound(src, dest), src));
+
+        while let Some((node, _)) = pq.delete_min() {
+            if nodes[node].visited {
+                continue;
+            }
+
+            nodes[node].visited = true;
+            if node == dest {
+                break;
+            }
+
+            let prio = nodes[node].dist;
+            let count = nodes[node].len + 1;
+
+            for (u, dist) in graph.neighbours(node) {
+                let dijnode = &mut nodes[u];
+                let alt = prio + *dist;
+                if !dijnode.visited && alt < dijnode.dist {
+                    dijnode.dist = alt;
+                    dijnode.pred = node;
+                    dijnode.len = count;
+                    dijnode.feasible = true;
+                    pq.insert(u, (alt + self.lower_bound(u, dest), u));
+                }
+            }
+        }
+
+        traverse_path(src, dest, &nodes)
+    }
+
+    /// Returns a lower bound of the distance from ```node``` to ```dest```.
+    fn lower_bound(&self, node: usize, dest: usize) -> W
+    where
+        W: Num + PartialOrd + Copy,
+    {
+        let mut bound = W::zero();
+        let mut raise = |a: Option<&Option<W>>, b: Option<&Option<W>>| {
+            // For unsigned weights, the difference must not be negative.
+            if let (Some(Some(a)), Some(Some(b))) = (a, b) {
+                if a > b && *a - *b > bound {
+                    bound = *a - *b;
+                }
+            }
+        };
+
+        for (from, to) in self.from.iter().zip(&self.to) {
+            // d(node, dest) >= d(node, L) - d(dest, L) and d(node, dest) >= d(L, dest) - d(L, node).
+            raise(to.get(node), to.get(dest));
+            raise(from.get(dest), from.get(node));
+        }
+
+        bound
+    }
+}
+
+/// Returns the distances from ```src``` to all nodes, or ```None``` for unreachable nodes.
+fn distances<W, E>(graph: &SimpleGraph<W, E>, src: usize, n_nodes: usize) -> Vec<Option<W>>
+where
+    W: Infinity + Num + Zero + PartialOrd + Copy,
+{
+    let mut dists = graph.sssp_dijkstra_lazy(src).distances();
+    dists.resize(n_nodes, None);
+    dists
+}
+
+/// Returns the node with the largest known distance that is not excluded. Ties are broken by the
+/// smallest index.
+fn farthest<W>(dists: &[Option<W>], excluded: &[usize]) -> Option<usize>
+where
+    W: PartialOrd + Copy,
+{
+    let mut best: Option<(usize, W)> = None;
+
+    for (ii, dist) in dists.iter().enumerate() {
+        if let Some(dist) = dist {
+            if excluded.contains(&ii) {
+                continue;
+            }
+
+            match best {
+                Some((_, max)) if *dist <= max => {}
+                _ => best = Some((ii, *dist)),
+            }
+        }
+    }
+
+    best.map(|(ii, _)| ii)
+}
@@ -0,0 +1,196 @@
+//! Generators of synthetic graphs, e.g. for benchmarks that shouldn't depend on an external data set.
+//!
+//! The random generators are deterministic: the same parameters and seed always produce the same graph,
+//! on every platform. They use a small built-in pseudo-random number generator, which is fast but not
+//! suitable for anything else than generating test data.
+//!
+//! # Examples
+//! ```
+//! use pheap::graph::generate::{erdos_renyi, grid_2d};
+//!
+//! let g = erdos_renyi::<u32>(1_000, 0.01, 1..100, 42);
+//! assert_eq!(1_000, g.n_nodes());
+//!
+//! // A 3x4 grid, in which every edge has the weight 1.
+//! let g = grid_2d(3, 4, |_, _| 1u32);
+//! assert_eq!(2 * 17, g.n_edges());
+//! ```
+
+use std::ops::Range;
+
+use num_traits::{Num, NumCast};
+
+use super::{grid_index, SimpleGraph};
+
+/// Creates a random graph with ```n``` nodes, in which each pair of nodes is connected with probability
+/// ```p```, following the Erdős–Rényi model.
+///
+/// The weights are drawn uniformly from ```weight_range```. The edges are generated by skipping over the
+/// unconnected pairs, which takes ```O(n + m)``` time for ```m``` edges instead of ```O(n^2)```.
+pub fn erdos_renyi<W>(n: usize, p: f64, weight_range: Range<W>, seed: u64) -> SimpleGraph<W>
+where
+    W: Num + NumCast + Copy + PartialOrd,
+{
+    let mut rng = SplitMix64::new(seed);
+    let mut graph = SimpleGraph::with_capacity(n);
+    for node in 0..n {
+        graph.add_node(node);
+    }
+
+    if p <= 0.0 {
+        return graph;
+    }
+
+    if p >= 1.0 {
+        for node1 in 0..n {
+            for node2 in 0..node1 {
+                graph.add_weighted_edges(node1, node2, uniform(&mut rng, &weight_range));
+            }
+        }
+
+        return graph;
+    }
+
+    // Batagelj and Brandes: the gaps between consecutive edges of the sequence of all pairs (v, w) with
+    // w < v are geometrically distributed.
+    let log_q = (1.0 - p).ln();
+    let mut node1 = 1;
+    let mut node2: usize = 0;
+
+    while node1 < n {
+        let skip = ((1.0 - rng.next_f64()).ln() / log_q).floor() as usize;
+        node2 = node2.saturating_add(skip);
+
+        while node2 >= node1 && node1 < n {
+            node2 -= node1;
+            node1 += 1;
+        }
+
+        if node1 < n {
+            graph.add_weighted_edges(node1, node2, uniform(&mut rng, &weight_range));
+            node2 += 1;
+        }
+    }
+
+    graph
+}
+
+/// Creates a grid graph with ```rows * cols``` nodes, in which each node is connected to its right and
+/// lower neighbour.
+///
+/// The node in row ```r``` and column ```c``` has the index ```r * cols + c```, see [`grid_index`]. The
+/// weight of the edge between two nodes is returned by ```weight_fn```, which is called with the smaller
+/// index first.
+pub fn grid_2d<W, F>(rows: usize, cols: usize, mut weight_fn: F) -> SimpleGraph<W>
+where
+    W: Copy + PartialOrd,
+    F: FnMut(usize, usize) -> W,
+{
+    let mut graph = SimpleGraph::with_capacity(rows * cols);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let node = grid_index(row, col, cols);
+            graph.add_node(node);
+
+            if col + 1 < cols {
+                graph.add_weighted_edges(node, node + 1, weight_fn(node, node + 1));
+            }
+
+            if row + 1 < rows {
+                graph.add_weighted_edges(node, node + cols, weight_fn(node, node + cols));
+            }
+        }
+    }
+
+    graph
+}
+
+/// Creates a random geometric graph with ```n``` nodes, which are placed uniformly in the unit square.
+/// Two nodes are connected if their Euclidean distance is at most ```radius```.
+///
+/// The weight of an edge is the distance between its nodes, converted to ```W```. For integer weights,
+/// scale the distances beforehand, e.g. with a larger square, or they will be truncated to ```0```.
+pub fn random_geometric<W>(n: usize, radius: f64, seed: u64) -> SimpleGraph<W>
+where
+    W: NumCast + Copy + PartialOrd,
+{
+    let mut rng = SplitMix64::new(seed);
+    let points: Vec<(f64, f64)> = (0..n).map(|_| (rng.next_f64(), rng.next_f64())).collect();
+
+    let mut graph = SimpleGraph::with_capacity(n);
+    for node in 0..n {
+        graph.add_node(node);
+    }
+
+    if radius <= 0.0 || n == 0 {
+        return graph;
+    }
+
+    // Only nodes in the same or an adjacent cell of a grid with cells of the size of the radius can be
+    // connected.
+    let side = ((1.0 / radius).floor() as usize).clamp(1, n);
+    let cell = |x: f64| ((x * side as f64) as usize).min(side - 1);
+    let mut cells = vec![Vec::new(); side * side];
+    for (node, (x, y)) in points.iter().enumerate() {
+        cells[cell(*y) * side + cell(*x)].push(node);
+    }
+
+    for (node1, (x1, y1)) in points.iter().enumerate() {
+        let (row, col) = (cell(*y1), cell(*x1));
+
+        for r in row.saturating_sub(1)..(row + 2).min(side) {
+            for c in col.saturating_sub(1)..(col + 2).min(side) {
+                for node2 in &cells[r * side + c] {
+                    if *node2 <= node1 {
+                        continue;
+                    }
+
+                    let (x2, y2) = points[*node2];
+                    let dist = (x1 - x2).hypot(y1 - y2);
+                    if dist <= radius {
+                        if let Some(weight) = <W as NumCast>::from(dist) {
+                            graph.add_weighted_edges(node1, *node2, weight);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+/// Draws a value uniformly from a range of integers or floating point numbers.
+fn uniform<W>(rng: &mut SplitMix64, range: &Range<W>) -> W
+where
+    W: Num + NumCast + Copy,
+{
+    let width = (range.end - range.start).to_f64().unwrap_or(0.0);
+    range.start + <W as NumCast>::from(rng.next_f64() * width).unwrap_or_else(W::zero)
+}
+
+/// A SplitMix64 pseudo-random number generator.
+#[derive(Debug)]
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in ```[0, 1)```.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
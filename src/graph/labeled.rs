@@ -0,0 +1,193 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash, ops::AddAssign};
+
+use num_traits::{Bounded, Num, Zero};
+
+use super::{mst_prim_edges, Infinity, ShortestPath, SimpleGraph};
+
+/// An undirected graph whose nodes are identified by labels of type ```N```, e.g. names, instead of indices.
+///
+/// [`SimpleGraph`] has no mapping from external objects to its node indices. [`LabeledGraph`] wraps a
+/// [`SimpleGraph`] together with a bidirectional mapping between labels and indices. A label gets the next
+/// free index when it is first added, so that the indices are dense and start from ```0```. Every label is
+/// stored twice, once in each direction of the mapping.
+///
+/// # Examples
+/// ```
+/// use pheap::graph::LabeledGraph;
+///
+/// let mut g = LabeledGraph::new();
+/// g.add_weighted_edges("Berlin", "Hamburg", 289);
+/// g.add_weighted_edges("Berlin", "Munich", 585);
+/// g.add_weighted_edges("Hamburg", "Cologne", 426);
+/// g.add_weighted_edges("Cologne", "Munich", 575);
+///
+/// let sp = &g.sssp_dijkstra("Hamburg", &["Munich"])[0];
+/// assert_eq!(874, sp.dist());
+/// assert_eq!(vec![&"Hamburg", &"Berlin", &"Munich"], g.path_labels(sp));
+/// ```
+#[derive(Debug)]
+pub struct LabeledGraph<N, W> {
+    graph: SimpleGraph<W>,
+    indices: HashMap<N, usize>,
+    labels: Vec<N>,
+}
+
+impl<N, W> LabeledGraph<N, W>
+where
+    N: Hash + Eq + Clone,
+{
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty graph with capacity for the given number of nodes.
+    pub fn with_capacity(n_nodes: usize) -> Self {
+        Self {
+            graph: SimpleGraph::with_capacity(n_nodes),
+            indices: HashMap::with_capacity(n_nodes),
+            labels: Vec::with_capacity(n_nodes),
+        }
+    }
+
+    /// Returns the number of nodes in the graph.
+    #[inline]
+    pub fn n_nodes(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns the number of edges in the graph, counted as in [`SimpleGraph::n_edges`].
+    #[inline]
+    pub fn n_edges(&self) -> usize {
+        self.graph.n_edges()
+    }
+
+    /// Returns the underlying graph, whose node indices are given by [`LabeledGraph::index`].
+    #[inline]
+    pub fn graph(&self) -> &SimpleGraph<W> {
+        &self.graph
+    }
+
+    /// Returns the index of the node with the given label, or ```None``` if there is no such node.
+    pub fn index<Q>(&self, label: &Q) -> Option<usize>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.indices.get(label).copied()
+    }
+
+    /// Returns the label of the node with the given index, or ```None``` if there is no such node.
+    #[inline]
+    pub fn label(&self, index: usize) -> Option<&N> {
+        self.labels.get(index)
+    }
+
+    /// Adds a node without any edges to the graph and returns its index.
+    ///
+    /// If the node already exists in the graph, only its index is returned.
+    pub fn add_node(&mut self, label: N) -> usize {
+        if let Some(index) = self.indices.get(&label) {
+            return *index;
+        }
+
+        let index = self.labels.len();
+        self.graph.add_node(index);
+        self.indices.insert(label.clone(), index);
+        self.labels.push(label);
+        index
+    }
+
+    /// Adds a weighted edge between two nodes to the graph, which are added first if they don't exist.
+    ///
+    /// See [`SimpleGraph::add_weighted_edges`] for details.
+    pub fn add_weighted_edges(&mut self, node1: N, node2: N, weight: W)
+    where
+        W: Copy + PartialOrd,
+    {
+        let node1 = self.add_node(node1);
+        let node2 = self.add_node(node2);
+        self.graph.add_weighted_edges(node1, node2, weight);
+    }
+
+    /// Returns the weight of the edge between two nodes, or ```None``` if there is no such edge.
+    pub fn edge_weight<Q>(&self, node1: &Q, node2: &Q) -> Option<&W>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.graph
+            .edge_weight(self.index(node1)?, self.index(node2)?)
+    }
+
+    /// Finds the shortest paths from a source node to destination nodes.
+    ///
+    /// The paths store node indices, see [`LabeledGraph::path_labels`] for their labels. The path to a
+    /// destination that is not in the graph is infeasible.
+    ///
+    /// # Panics
+    /// Panics if the source node is not in the graph.
+    pub fn sssp_dijkstra<Q>(&self, src: &Q, dest: &[N]) -> Vec<ShortestPath<W>>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Infinity + Num + Zero + PartialOrd + Copy,
+    {
+        let src = self
+            .index(src)
+            .expect("the source node is not in the graph");
+
+        // An index beyond the last node is never reachable.
+        let dest: Vec<usize> = dest
+            .iter()
+            .map(|node| {
+                self.indices
+                    .get::<N>(node)
+                    .copied()
+                    .unwrap_or(self.labels.len())
+            })
+            .collect();
+
+        self.graph.sssp_dijkstra(src, &dest)
+    }
+
+    /// Returns the labels of the nodes of a path found in this graph, from the source to the destination
+    /// node.
+    pub fn path_labels(&self, path: &ShortestPath<W>) -> Vec<&N> {
+        path.path().iter().map(|node| &self.labels[*node]).collect()
+    }
+
+    /// Finds the minimum spanning tree of the graph with Prim's algorithm, see [`mst_prim_edges`].
+    ///
+    /// Returns the edges of the tree as pairs of labels together with their weights, and the total weight.
+    ///
+    /// # Panics
+    /// Panics if the source node is not in the graph.
+    pub fn mst_prim<Q>(&self, src: &Q) -> (Vec<(&N, &N, W)>, W)
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        W: Copy + PartialOrd + Bounded + Zero + AddAssign,
+    {
+        let src = self
+            .index(src)
+            .expect("the source node is not in the graph");
+        let (edges, dist) = mst_prim_edges(&self.graph, src);
+
+        let edges = edges
+            .into_iter()
+            .map(|(node1, node2, w)| (&self.labels[node1], &self.labels[node2], w))
+            .collect();
+
+        (edges, dist)
+    }
+}
+
+impl<N, W> Default for LabeledGraph<N, W>
+where
+    N: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
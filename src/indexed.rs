@@ -0,0 +1,125 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use crate::{ph::HeapElmt, PairingHeap};
+
+/// A min-pairing heap with unique keys, which keeps an index from each key to its element.
+///
+/// [`PairingHeap::decrease_prio`] and [`PairingHeap::change_prio_by_key`] have to search the heap for a
+/// key, which takes ```O(n)``` time. With the index, the element of a key is found in ```O(1)```, so that
+/// its priority is changed in ```O(log n)``` amortized time. In return, every key is stored twice and each
+/// insertion and deletion updates the index. Use [`PairingHeap`] if priorities are never changed by key.
+///
+/// # Examples
+/// ```
+/// use pheap::IndexedPairingHeap;
+///
+/// let mut heap = IndexedPairingHeap::new_indexed();
+/// heap.insert("a", 10);
+/// heap.insert("b", 20);
+///
+/// assert_eq!(Some(20), heap.decrease_prio_indexed("b", 5));
+/// assert_eq!(Some(&5), heap.get_prio("b"));
+/// assert_eq!(Some(("b", 5)), heap.delete_min());
+/// assert!(!heap.contains_key("b"));
+/// ```
+#[derive(Debug)]
+pub struct IndexedPairingHeap<K, P> {
+    heap: PairingHeap<K, P>,
+    index: HashMap<K, HeapElmt<K, P>>,
+}
+
+impl<K, P> IndexedPairingHeap<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: PartialOrd,
+{
+    /// Creates an empty heap with a key index.
+    pub fn new_indexed() -> Self {
+        Self {
+            heap: PairingHeap::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of elements stored in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks whether the heap is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Checks whether the heap contains an element with the given key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// Returns the priority of the given key, or ```None``` if the key is not in the heap.
+    pub fn get_prio<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.get(key).and_then(|elmt| self.heap.prio(elmt))
+    }
+
+    /// Returns the element with the minimum priority.
+    #[inline]
+    pub fn find_min(&self) -> Option<(&K, &P)> {
+        self.heap.find_min()
+    }
+
+    /// Inserts a new element to the heap and returns ```None```.
+    ///
+    /// If the key is already in the heap, its priority is changed to ```prio``` instead, and the previous
+    /// priority is returned.
+    pub fn insert(&mut self, key: K, prio: P) -> Option<P> {
+        match self.index.get(&key) {
+            Some(elmt) => self.heap.change_prio(elmt, prio),
+            None => {
+                let elmt = self.heap.insert2(key.clone(), prio);
+                self.index.insert(key, elmt);
+                None
+            }
+        }
+    }
+
+    /// Changes the priority of a key to ```new_prio``` and returns its previous priority.
+    ///
+    /// The element is looked up in the index, so that only the update itself depends on the size of the
+    /// heap. Increasing the priority is supported as well. Returns ```None``` if the key is not found in
+    /// the heap, in which case the heap remains unchanged.
+    pub fn decrease_prio_indexed<Q>(&mut self, key: &Q, new_prio: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let elmt = self.index.get(key)?;
+        self.heap.change_prio(elmt, new_prio)
+    }
+
+    /// Deletes the element with the minimum priority and returns its key and priority.
+    pub fn delete_min(&mut self) -> Option<(K, P)> {
+        let (key, prio) = self.heap.delete_min()?;
+        self.index.remove(&key);
+        Some((key, prio))
+    }
+}
+
+impl<K, P> Default for IndexedPairingHeap<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: PartialOrd,
+{
+    fn default() -> Self {
+        Self::new_indexed()
+    }
+}
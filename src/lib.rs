@@ -23,7 +23,22 @@
 )]
 
 mod ph;
-pub use ph::PairingHeap;
+pub use ph::{DeleteStrategy, Drain, PairingHeap, ValueHeap};
+
+#[cfg(feature = "instrument")]
+pub use ph::OpCounts;
+
+mod by_key;
+pub use by_key::PairingHeapByKey;
+
+mod max;
+pub use max::MaxPairingHeap;
+
+mod stable;
+pub use stable::StablePairingHeap;
+
+mod indexed;
+pub use indexed::IndexedPairingHeap;
 
 /// Experimental API for graph analysis.
 pub mod graph;
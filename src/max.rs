@@ -0,0 +1,110 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::PairingHeap;
+
+/// A max-pairing heap, which returns the element with the maximum priority first.
+///
+/// The heap is a [`PairingHeap`] whose priorities are wrapped in [`Reverse`]. Since the pairing heap
+/// compares priorities only with their ```PartialOrd``` implementation, all operations, including
+/// ```merge```, keep the element with the larger priority at the root.
+///
+/// # Examples
+/// ```
+/// use pheap::MaxPairingHeap;
+///
+/// let mut heap = MaxPairingHeap::new();
+/// heap.insert("a", 10);
+/// heap.insert("b", 20);
+///
+/// assert_eq!(Some((&"b", &20)), heap.find_max());
+/// assert_eq!(Some(("b", 20)), heap.delete_max());
+/// assert_eq!(1, heap.len());
+/// ```
+#[derive(Debug, Default)]
+pub struct MaxPairingHeap<K, P> {
+    heap: PairingHeap<K, Reverse<P>>,
+}
+
+impl<K, P> MaxPairingHeap<K, P> {
+    /// Creates an empty max-pairing heap.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            heap: PairingHeap::new(),
+        }
+    }
+
+    /// Returns the number of elements stored in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks whether the heap is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the element with the maximum priority.
+    #[inline]
+    pub fn find_max(&self) -> Option<(&K, &P)> {
+        self.heap.find_min().map(|(key, prio)| (key, &prio.0))
+    }
+
+    /// Merges two heaps together and forms a new heap.
+    pub fn merge(self, other: Self) -> Self
+    where
+        P: PartialOrd,
+    {
+        Self {
+            heap: self.heap.merge(other.heap),
+        }
+    }
+
+    /// Inserts a new element to the heap.
+    #[inline]
+    pub fn insert(&mut self, key: K, prio: P)
+    where
+        P: PartialOrd,
+    {
+        self.heap.insert(key, Reverse(prio));
+    }
+
+    /// Deletes the element with the maximum priority and returns it.
+    #[inline]
+    pub fn delete_max(&mut self) -> Option<(K, P)>
+    where
+        P: PartialOrd,
+    {
+        self.heap.delete_min().map(|(key, prio)| (key, prio.0))
+    }
+}
+
+/// Converts a heap of the standard library into a max-pairing heap, whose keys and priorities are the values.
+///
+/// Both heaps are max-heaps and return the values in the same order. This takes ```O(n)``` time.
+impl<T> From<BinaryHeap<T>> for MaxPairingHeap<T, T>
+where
+    T: Ord + Clone,
+{
+    fn from(heap: BinaryHeap<T>) -> Self {
+        Self {
+            heap: PairingHeap::from_iter_by(heap, |value| Reverse(value.clone())),
+        }
+    }
+}
+
+/// Converts a max-pairing heap, whose keys and priorities are the same values, into a heap of the standard
+/// library. Both heaps return the values in the same order.
+///
+/// Only heaps whose keys are their priorities can be converted, so that no key is lost. This takes ```O(n)```
+/// time.
+impl<T> From<MaxPairingHeap<T, T>> for BinaryHeap<T>
+where
+    T: Ord,
+{
+    fn from(mut heap: MaxPairingHeap<T, T>) -> Self {
+        heap.heap.drain().map(|(value, _)| value).collect()
+    }
+}
@@ -1,19 +1,137 @@
-use std::{collections::VecDeque, ops::SubAssign, ptr::NonNull};
+use std::{
+    borrow::Borrow,
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BinaryHeap, VecDeque},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::SubAssign,
+    ptr::NonNull,
+};
+
+#[cfg(feature = "rand")]
+use num_traits::ToPrimitive;
 
 /// A min-pairing heap data structure.
+///
+/// Besides its key and priority, every element can carry a payload of type ```D```, e.g. some state of
+/// an algorithm that would otherwise be stored in a separate vector. The payload is not involved in any
+/// comparison and defaults to ```()```. Elements with a payload are managed with
+/// [`PairingHeap::insert_with`], [`PairingHeap::find_min_with`] and [`PairingHeap::delete_min_with`].
 #[derive(Debug)]
-pub struct PairingHeap<K, P> {
-    root: Option<NonNull<Inner<K, P>>>,
+pub struct PairingHeap<K, P, D = ()> {
+    root: Option<NonNull<Inner<K, P, D>>>,
     len: usize,
+    /// How the children of the root are combined in [`PairingHeap::delete_min`].
+    delete_strategy: DeleteStrategy,
+    /// Operation counts, if the heap was created with [`PairingHeap::new_instrumented`].
+    #[cfg(feature = "instrument")]
+    counts: Option<OpCounts>,
 }
 
-impl<K, P> PairingHeap<K, P> {
+/// Counts of the basic operations performed by a heap created with [`PairingHeap::new_instrumented`].
+#[cfg(feature = "instrument")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OpCounts {
+    /// Number of times the root of one tree was linked below the root of another tree.
+    pub melds: u64,
+    /// Number of comparisons between the priorities of two elements.
+    pub comparisons: u64,
+    /// Number of allocated nodes.
+    pub allocations: u64,
+}
+
+/// The way in which [`PairingHeap::delete_min`] combines the children of the removed root into a new tree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DeleteStrategy {
+    /// Pairs up the children from left to right, then merges the pairs from right to left. This is the
+    /// default.
+    #[default]
+    TwoPass,
+    /// Pairs up the children from left to right and repeats on the resulting trees until only one
+    /// tree is left.
+    Multipass,
+}
+
+impl<K, P, D> PairingHeap<K, P, D> {
     /// Creates an empty pairing heap.
     #[inline]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Creates an empty pairing heap, which counts the melds, comparisons and allocations it performs.
+    ///
+    /// The counts are returned by [`PairingHeap::op_counts`]. This is only available with the ```instrument```
+    /// feature. Without it, no counting code is compiled in.
+    #[cfg(feature = "instrument")]
+    #[inline]
+    pub fn new_instrumented() -> Self {
+        Self {
+            counts: Some(OpCounts::default()),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the operation counts of a heap created with [`PairingHeap::new_instrumented`], or
+    /// ```None``` for any other heap.
+    #[cfg(feature = "instrument")]
+    pub fn op_counts(&self) -> Option<OpCounts> {
+        self.counts
+    }
+
+    /// Adds to the operation counts of an instrumented heap.
+    #[inline(always)]
+    fn record(&mut self, melds: u64, comparisons: u64, allocations: u64) {
+        #[cfg(feature = "instrument")]
+        if let Some(counts) = &mut self.counts {
+            counts.melds += melds;
+            counts.comparisons += comparisons;
+            counts.allocations += allocations;
+        }
+
+        #[cfg(not(feature = "instrument"))]
+        let _ = (melds, comparisons, allocations);
+    }
+
+    /// Creates an empty pairing heap, which combines the children of the root with the given strategy
+    /// whenever the minimum element is deleted.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::{DeleteStrategy, PairingHeap};
+    ///
+    /// let mut ph = PairingHeap::with_delete_strategy(DeleteStrategy::Multipass);
+    /// ph.insert(1, 10);
+    /// ph.insert(2, 5);
+    /// assert_eq!(Some((2, 5)), ph.delete_min());
+    /// ```
+    #[inline]
+    pub fn with_delete_strategy(strategy: DeleteStrategy) -> Self {
+        Self {
+            delete_strategy: strategy,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the strategy used to combine the children of the root in [`PairingHeap::delete_min`].
+    #[inline]
+    pub fn delete_strategy(&self) -> DeleteStrategy {
+        self.delete_strategy
+    }
+
+    /// Creates an empty pairing heap whose priorities are totally ordered.
+    ///
+    /// The heap itself only relies on [`PartialOrd`] for comparisons. For priorities such as floats,
+    /// incomparable values (e.g. ```NaN```) silently break the heap order. Requiring [`Ord`] rules this
+    /// out at compile time, so that every comparison between two priorities is well-defined.
+    #[inline]
+    pub fn new_ord() -> Self
+    where
+        P: Ord,
+    {
+        Self::default()
+    }
+
     /// Returns the number of elements stored in the heap.
     #[inline]
     pub fn len(&self) -> usize {
@@ -26,6 +144,136 @@ impl<K, P> PairingHeap<K, P> {
         self.len == 0
     }
 
+    /// Returns the approximate number of bytes consumed by the heap.
+    ///
+    /// The estimate covers the heap itself and all of its nodes, but not any memory that keys or
+    /// priorities own indirectly (e.g. the buffer of a ```String```).
+    #[inline]
+    pub fn memory_estimate(&self) -> usize {
+        std::mem::size_of::<Self>() + self.len * std::mem::size_of::<Inner<K, P, D>>()
+    }
+
+    /// Returns the number of nodes on the longest path from the root to a leaf of the heap's tree, or
+    /// ```0``` if the heap is empty.
+    ///
+    /// Together with [`PairingHeap::max_children`], this helps detecting degenerated heaps, e.g. long
+    /// chains, which slow down the heap operations. This takes ```O(n)``` time.
+    pub fn max_depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack: Vec<_> = self.root.into_iter().map(|root| (root, 1)).collect();
+
+        while let Some((node, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+            let mut child = unsafe { node.as_ref().left };
+
+            while let Some(c) = child {
+                stack.push((c, depth + 1));
+                child = unsafe { c.as_ref().right };
+            }
+        }
+
+        max_depth
+    }
+
+    /// Returns the largest number of children of a node in the heap's tree. This takes ```O(n)``` time.
+    pub fn max_children(&self) -> usize {
+        let mut max_children = 0;
+
+        for node in self.nodes() {
+            let mut count = 0;
+            let mut child = unsafe { node.as_ref().left };
+
+            while let Some(c) = child {
+                count += 1;
+                child = unsafe { c.as_ref().right };
+            }
+
+            max_children = max_children.max(count);
+        }
+
+        max_children
+    }
+
+    /// Returns the number of elements per priority, sorted by priority.
+    ///
+    /// This shows the distribution of the pending elements without removing them. Each distinct priority
+    /// is cloned once. This takes ```O(n log m)``` time for ```m``` distinct priorities.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// heap.insert("a", 2);
+    /// heap.insert("b", 1);
+    /// heap.insert("c", 2);
+    ///
+    /// let histogram: Vec<_> = heap.priority_histogram().into_iter().collect();
+    /// assert_eq!(vec![(1, 1), (2, 2)], histogram);
+    /// ```
+    pub fn priority_histogram(&self) -> BTreeMap<P, usize>
+    where
+        P: Ord + Clone,
+    {
+        let mut histogram = BTreeMap::new();
+
+        for (_, prio) in self.elements() {
+            match histogram.get_mut(prio) {
+                Some(count) => *count += 1,
+                None => {
+                    histogram.insert(prio.clone(), 1);
+                }
+            }
+        }
+
+        histogram
+    }
+
+    /// Checks whether the heap satisfies its invariants.
+    ///
+    /// A heap is valid if no child has a smaller priority than its parent, every node is linked
+    /// correctly to its parent, and the number of nodes matches [`PairingHeap::len`]. This takes
+    /// ```O(n)``` time.
+    pub fn is_valid(&self) -> bool
+    where
+        P: PartialOrd,
+    {
+        let root = match self.root {
+            Some(root) => root,
+            None => return self.len == 0,
+        };
+
+        let mut count = 0;
+        let mut stack = vec![root];
+
+        unsafe {
+            if root.as_ref().parent.is_some() || root.as_ref().right.is_some() {
+                return false;
+            }
+
+            while let Some(node) = stack.pop() {
+                count += 1;
+                let mut child = node.as_ref().left;
+
+                while let Some(c) = child {
+                    if c.as_ref().parent != Some(node) || c.as_ref().precedes(node.as_ref()) {
+                        return false;
+                    }
+
+                    // Guards against cycles in corrupted heaps.
+                    if count + stack.len() > self.len {
+                        return false;
+                    }
+
+                    stack.push(c);
+                    child = c.as_ref().right;
+                }
+            }
+        }
+
+        count == self.len
+    }
+
     /// Returns the minimum element, which is the root element, and its priority in a tuple of the heap.
     #[inline]
     pub fn find_min(&self) -> Option<(&K, &P)> {
@@ -38,36 +286,153 @@ impl<K, P> PairingHeap<K, P> {
         }
     }
 
+    /// Returns the minimum element together with its payload.
+    #[inline]
+    pub fn find_min_with(&self) -> Option<(&K, &P, &D)> {
+        self.root.map(|node| unsafe {
+            let r = &*node.as_ptr();
+            (&r.key, &r.prio, &r.data)
+        })
+    }
+
+    /// Returns the ```n``` elements with the smallest priorities in ascending order, without modifying
+    /// the heap. If the heap holds fewer than ```n``` elements, all of them are returned.
+    ///
+    /// Since every node precedes its children, only the children of the returned elements must be
+    /// considered as candidates. These are kept in a separate small heap, so that this takes
+    /// ```O(n log n)``` time plus the time to visit the children of the returned elements, instead of
+    /// sorting the whole heap.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// for (key, prio) in vec![("c", 3), ("a", 1), ("d", 4), ("b", 2)] {
+    ///     heap.insert(key, prio);
+    /// }
+    ///
+    /// assert_eq!(vec![(&"a", &1), (&"b", &2)], heap.peek_n(2));
+    /// assert_eq!(4, heap.len());
+    /// ```
+    pub fn peek_n(&self, n: usize) -> Vec<(&K, &P)>
+    where
+        P: PartialOrd,
+    {
+        let mut result = Vec::with_capacity(n.min(self.len));
+        let mut candidates = PairingHeap::<&Inner<K, P, D>, &P>::new();
+
+        if let Some(root) = self.root {
+            let root = unsafe { &*root.as_ptr() };
+            candidates.insert(root, &root.prio);
+        }
+
+        while result.len() < n {
+            let node = match candidates.delete_min() {
+                Some((node, _)) => node,
+                None => break,
+            };
+
+            result.push((&node.key, &node.prio));
+
+            let mut child = node.left;
+            while let Some(c) = child {
+                let c = unsafe { &*c.as_ptr() };
+                candidates.insert(c, &c.prio);
+                child = c.right;
+            }
+        }
+
+        result
+    }
+
+    /// Returns a mutable reference to the payload of the minimum element.
+    ///
+    /// Since the payload doesn't affect the order of the elements, it can be modified in place.
+    #[inline]
+    pub fn find_min_data_mut(&mut self) -> Option<&mut D> {
+        self.root.map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
     /// Merges two heaps together and forms a new heap.
     ///
     /// If one heap is empty, the other heap will be returned and vice versa. Otherwise, a new heap
     /// will be created, whose root is the root that has a smaller value. The other root will be
     /// inserted in the new heap.
+    ///
+    /// The two heaps must not share any node. This is always the case for heaps built with the safe API
+    /// of this crate, but not for a heap and a shallow copy of itself (e.g. obtained with
+    /// ```std::ptr::read```), which leads to a use-after-free. The precondition is not checked, so that
+    /// merging takes ```O(1)``` time in all builds.
     #[inline]
-    pub fn merge(mut self, mut other: Self) -> Self
+    pub fn merge(self, other: Self) -> Self
     where
         P: PartialOrd,
+    {
+        self.merge_by(other, &ByPrio)
+    }
+
+    /// Merges two heaps together, whose elements are ordered by ```order```, and forms a new heap.
+    pub(crate) fn merge_by<O>(mut self, mut other: Self, order: &O) -> Self
+    where
+        O: Order<K, P>,
     {
         let len = self.len() + other.len();
-        let root = Self::merge_nodes(self.root, other.root);
+        let root = self.merge_nodes_by(self.root, other.root, order);
 
         self.root = None;
         other.root = None;
 
-        Self { root, len }
+        Self {
+            root,
+            len,
+            delete_strategy: self.delete_strategy,
+            #[cfg(feature = "instrument")]
+            counts: self.counts,
+        }
+    }
+
+    /// Moves all elements of ```other``` into this heap in ```O(1)``` time.
+    ///
+    /// This is the in-place form of [`PairingHeap::merge`]: the root of ```other``` is melded into the root
+    /// of this heap, so no node is reallocated. The same precondition as for [`PairingHeap::merge`] applies.
+    #[inline]
+    pub fn absorb(&mut self, mut other: Self)
+    where
+        P: PartialOrd,
+    {
+        self.root = self.merge_nodes(self.root, other.root.take());
+        self.len += other.len;
+        other.len = 0;
     }
 
     #[inline]
     fn merge_nodes(
-        node1: Option<NonNull<Inner<K, P>>>,
-        node2: Option<NonNull<Inner<K, P>>>,
-    ) -> Option<NonNull<Inner<K, P>>>
+        &mut self,
+        node1: Option<NonNull<Inner<K, P, D>>>,
+        node2: Option<NonNull<Inner<K, P, D>>>,
+    ) -> Option<NonNull<Inner<K, P, D>>>
     where
         P: PartialOrd,
+    {
+        self.merge_nodes_by(node1, node2, &ByPrio)
+    }
+
+    #[inline]
+    fn merge_nodes_by<O>(
+        &mut self,
+        node1: Option<NonNull<Inner<K, P, D>>>,
+        node2: Option<NonNull<Inner<K, P, D>>>,
+        order: &O,
+    ) -> Option<NonNull<Inner<K, P, D>>>
+    where
+        O: Order<K, P>,
     {
         match (node1, node2) {
             (Some(root1), Some(root2)) => unsafe {
-                let root = if root1.as_ref().prio < root2.as_ref().prio {
+                self.record(1, 1, 0);
+                let (r1, r2) = (root1.as_ref(), root2.as_ref());
+                let root = if order.precedes(&r1.key, &r1.prio, &r2.key, &r2.prio) {
                     Self::meld(root1, root2)
                 } else {
                     Self::meld(root2, root1)
@@ -82,251 +447,1034 @@ impl<K, P> PairingHeap<K, P> {
 
     #[inline(always)]
     unsafe fn meld(
-        node1: NonNull<Inner<K, P>>,
-        node2: NonNull<Inner<K, P>>,
-    ) -> NonNull<Inner<K, P>> {
+        node1: NonNull<Inner<K, P, D>>,
+        node2: NonNull<Inner<K, P, D>>,
+    ) -> NonNull<Inner<K, P, D>> {
         (*node2.as_ptr()).parent = Some(node1);
         (*node2.as_ptr()).right = node1.as_ref().left;
         (*node1.as_ptr()).left = Some(node2);
         node1
     }
 
-    /// Inserts a new element to the heap.
+    /// Inserts a new element with a payload to the heap.
     #[inline]
-    pub fn insert(&mut self, key: K, prio: P)
+    pub fn insert_with(&mut self, key: K, prio: P, data: D)
     where
         P: PartialOrd,
     {
-        self.insert2(key, prio);
+        self.insert_node(key, prio, data);
     }
 
-    // Expose HeapElmt to pub, no?
     #[inline]
-    pub(crate) fn insert2(&mut self, key: K, prio: P) -> HeapElmt<K, P>
+    fn insert_node(&mut self, key: K, prio: P, data: D) -> HeapElmt<K, P, D>
     where
         P: PartialOrd,
     {
-        let node = NonNull::new(Box::leak(Box::new(Inner::new(key, prio))));
+        self.insert_by(key, prio, data, &ByPrio)
+    }
 
-        self.root = Self::merge_nodes(self.root, node);
+    /// Inserts a new element with a payload to a heap, whose elements are ordered by ```order```.
+    #[inline]
+    pub(crate) fn insert_by<O>(&mut self, key: K, prio: P, data: D, order: &O) -> HeapElmt<K, P, D>
+    where
+        O: Order<K, P>,
+    {
+        let node = Some(self.new_node(key, prio, data));
+        self.root = self.merge_nodes_by(self.root, node, order);
         self.len += 1;
 
         HeapElmt { inner: node }
     }
 
-    /// Decreases the priority of a key by the amount given in ```delta```.
-    pub fn decrease_prio(&mut self, key: &K, delta: P)
+    /// Allocates a detached node.
+    fn new_node(&mut self, key: K, prio: P, data: D) -> NonNull<Inner<K, P, D>> {
+        self.record(0, 0, 1);
+        NonNull::from(Box::leak(Box::new(Inner::new(key, prio, data))))
+    }
+
+    /// Decreases the priority of a key by the amount given in ```delta``` and returns its previous priority.
+    ///
+    /// The key may be any borrowed form of the heap's key type, e.g. ```&str``` for ```String``` keys.
+    /// Returns ```None``` if the key is not found in the heap, in which case the heap remains unchanged.
+    pub fn decrease_prio<Q>(&mut self, key: &Q, delta: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+        P: PartialOrd + SubAssign + Clone,
+    {
+        let node = self.find_node(key)?;
+
+        unsafe {
+            let old_prio = node.as_ref().prio.clone();
+            (*node.as_ptr()).prio -= delta;
+            self.decrease(node);
+            Some(old_prio)
+        }
+    }
+
+    /// Decreases the priorities of many keys at once, each by the amount given alongside the key.
+    ///
+    /// Unlike calling [`PairingHeap::decrease_prio`] for each key, which searches the heap once per key,
+    /// this method traverses the heap only once and then merges all nodes that violate the heap order
+    /// back into the heap in one go.
+    pub fn decrease_prio_many(&mut self, updates: &[(K, P)])
     where
         K: PartialEq,
-        P: PartialOrd + SubAssign,
+        P: PartialOrd + SubAssign + Clone,
     {
-        if let Some(root) = self.root {
+        if updates.is_empty() {
+            return;
+        }
+
+        let mut changed = Vec::new();
+        let mut stack: Vec<_> = self.root.into_iter().collect();
+
+        while let Some(node) = stack.pop() {
             unsafe {
-                if &root.as_ref().key == key {
-                    (*root.as_ptr()).prio -= delta;
-                    return;
+                let inner = &mut *node.as_ptr();
+                let mut hit = false;
+
+                for (key, delta) in updates {
+                    if &inner.key == key {
+                        inner.prio -= delta.clone();
+                        hit = true;
+                    }
                 }
 
-                let mut targ = None;
-                let mut prev = None;
-                let mut tmp_nodes = VecDeque::with_capacity(self.len << 2);
-                let mut traverse = root.as_ref().left;
+                if hit {
+                    changed.push(node);
+                }
 
-                while let Some(node) = traverse {
-                    if &node.as_ref().key == key {
-                        targ = traverse;
-                        break;
-                    }
+                stack.extend(inner.left);
+                stack.extend(inner.right);
+            }
+        }
 
-                    prev = traverse;
-                    tmp_nodes.push_back(traverse);
-
-                    if node.as_ref().right.is_some() {
-                        traverse = node.as_ref().right;
-                    } else {
-                        while let Some(front) = tmp_nodes.pop_front() {
-                            traverse = front.unwrap().as_ref().left;
-                            if traverse.is_some() {
-                                break;
-                            }
-                        }
+        // Cut nodes are linked as siblings, so that they can be paired up like the children of a root.
+        let mut cuts = None;
+
+        for node in changed {
+            unsafe {
+                if let Some(parent) = node.as_ref().parent {
+                    self.record(0, 1, 0);
+                    if !parent.as_ref().precedes(node.as_ref()) {
+                        self.cut(node);
+                        (*node.as_ptr()).right = cuts;
+                        cuts = Some(node);
                     }
                 }
+            }
+        }
 
-                if let Some(node) = targ {
-                    // Every node must have a parent. So unwrap() here shouldn't panic.
-                    let parent = node.as_ref().parent.unwrap();
-                    (*node.as_ptr()).prio -= delta;
+        unsafe {
+            let cuts = self.merge_pairs(cuts);
+            self.root = self.merge_nodes(self.root, cuts);
+        }
+    }
 
-                    if parent.as_ref().prio < node.as_ref().prio {
-                        return;
-                    }
+    pub(crate) fn update_prio(&mut self, node: &HeapElmt<K, P, D>, new_prio: P)
+    where
+        P: PartialOrd,
+    {
+        self.change_prio(node, new_prio);
+    }
 
-                    if parent.as_ref().left == targ {
-                        (*parent.as_ptr()).left = node.as_ref().right;
-                    }
+    /// Changes the priority of the element referenced by a handle and returns its previous priority.
+    ///
+    /// Both decreasing and increasing the priority are supported. Returns ```None``` if the handle
+    /// doesn't point to any element.
+    ///
+    /// Handles are only used inside the crate, e.g. by Dijkstra's algorithm, because a handle is not
+    /// invalidated when its element leaves the heap. The public API is [`PairingHeap::change_prio_by_key`].
+    pub(crate) fn change_prio(&mut self, elmt: &HeapElmt<K, P, D>, new_prio: P) -> Option<P>
+    where
+        P: PartialOrd,
+    {
+        elmt.inner
+            .map(|node| unsafe { self.change(node, new_prio) })
+    }
 
-                    if let Some(prev_node) = prev {
-                        if prev_node.as_ref().right == targ {
-                            (*prev_node.as_ptr()).right = node.as_ref().right;
-                        }
-                    }
+    /// Returns the priority of the element referenced by a handle, or ```None``` if the handle doesn't
+    /// point to any element.
+    pub(crate) fn prio(&self, elmt: &HeapElmt<K, P, D>) -> Option<&P> {
+        elmt.inner.map(|node| unsafe { &(*node.as_ptr()).prio })
+    }
 
-                    (*node.as_ptr()).parent = None;
-                    (*node.as_ptr()).right = None;
+    /// Changes the priority of a key to ```new_prio``` and returns its previous priority.
+    ///
+    /// Both decreasing and increasing the priority are supported. Returns ```None``` if the key
+    /// is not found in the heap, in which case the heap remains unchanged.
+    ///
+    /// This is the only public way to change the priority of an element, since the handles returned by
+    /// the internal insertion are not exposed.
+    pub fn change_prio_by_key<Q>(&mut self, key: &Q, new_prio: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+        P: PartialOrd,
+    {
+        self.map_prio_by_key(key, |_| new_prio)
+    }
+
+    /// Changes the priority of a key to the one computed from its previous priority by ```f```, and
+    /// returns the previous priority.
+    pub(crate) fn map_prio_by_key<Q, F>(&mut self, key: &Q, f: F) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+        P: PartialOrd,
+        F: FnOnce(&P) -> P,
+    {
+        let node = self.find_node(key)?;
+
+        unsafe {
+            let new_prio = f(&node.as_ref().prio);
+            Some(self.change(node, new_prio))
+        }
+    }
+
+    /// Removes all elements with the given key and returns their priorities in arbitrary order.
+    ///
+    /// Unlike [`PairingHeap::decrease_prio`], which only affects the first element found, this also
+    /// removes duplicates of a key, e.g. stale entries that were inserted again with a new priority.
+    /// The heap is scanned once, which takes ```O(n)``` time.
+    pub fn remove_key<Q>(&mut self, key: &Q) -> Vec<P>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+        P: PartialOrd,
+    {
+        let matches: Vec<_> = self
+            .nodes()
+            .into_iter()
+            .filter(|node| unsafe { node.as_ref() }.key.borrow() == key)
+            .collect();
+
+        matches
+            .into_iter()
+            .map(|node| unsafe { self.remove_node(node).1 })
+            .collect()
+    }
+
+    /// Collects references to all elements of the heap in arbitrary order.
+    fn elements(&self) -> Vec<(&K, &P)> {
+        self.nodes()
+            .into_iter()
+            .map(|node| unsafe {
+                let node = &*node.as_ptr();
+                (&node.key, &node.prio)
+            })
+            .collect()
+    }
+
+    /// Checks whether two heaps don't share any node, which is a precondition of [`PairingHeap::merge`].
+    #[cfg(test)]
+    pub(crate) fn is_disjoint(&self, other: &Self) -> bool {
+        if self.root.is_none() || other.root.is_none() {
+            return true;
+        }
+
+        let nodes: std::collections::HashSet<_> = self.nodes().into_iter().collect();
+        other.nodes().iter().all(|node| !nodes.contains(node))
+    }
+
+    /// Collects pointers to all nodes of the heap in arbitrary order.
+    fn nodes(&self) -> Vec<NonNull<Inner<K, P, D>>> {
+        let mut nodes = Vec::with_capacity(self.len);
+        let mut stack: Vec<_> = self.root.into_iter().collect();
+
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            unsafe {
+                stack.extend(node.as_ref().left);
+                stack.extend(node.as_ref().right);
+            }
+        }
+
+        nodes
+    }
+
+    /// Collects references to all elements of the heap, sorted by key and then by priority.
+    fn sorted_elements(&self) -> Vec<(&K, &P)>
+    where
+        K: Ord,
+        P: Ord,
+    {
+        let mut elements = self.elements();
+        elements.sort_unstable();
+        elements
+    }
+
+    /// Searches the heap for the node storing the given key.
+    fn find_node<Q>(&self, key: &Q) -> Option<NonNull<Inner<K, P, D>>>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        let mut stack = Vec::new();
+        let mut traverse = self.root;
 
-                    self.root = Self::merge_nodes(self.root, targ);
+        while let Some(node) = traverse {
+            unsafe {
+                if node.as_ref().key.borrow() == key {
+                    return Some(node);
+                }
+
+                if let Some(right) = node.as_ref().right {
+                    stack.push(right);
                 }
+
+                traverse = node.as_ref().left.or_else(|| stack.pop());
             }
         }
+
+        None
     }
 
-    // TODO: currently only works when new_prio < prio.
-    pub(crate) fn update_prio(&mut self, node: &HeapElmt<K, P>, new_prio: P)
+    unsafe fn change(&mut self, node: NonNull<Inner<K, P, D>>, new_prio: P) -> P
     where
         P: PartialOrd,
     {
-        unsafe {
-            self.update(node.inner, new_prio);
+        let decrease = new_prio < node.as_ref().prio;
+        let old_prio = std::mem::replace(&mut (*node.as_ptr()).prio, new_prio);
+
+        if decrease {
+            self.decrease(node);
+        } else if let Some(children) = (*node.as_ptr()).left.take() {
+            // The children of the node might now have a smaller priority than the node itself.
+            // So they are detached and paired up again before being merged back into the heap.
+            let children = self.merge_pairs(Some(children));
+
+            if node.as_ref().parent.is_some() {
+                self.cut(node);
+                let subtree = self.merge_nodes(Some(node), children);
+                self.root = self.merge_nodes(self.root, subtree);
+            } else {
+                self.root = self.merge_nodes(children, Some(node));
+            }
         }
+
+        old_prio
     }
 
-    unsafe fn update(&mut self, targ: Option<NonNull<Inner<K, P>>>, new_prio: P)
+    /// Restores the heap order after the priority of a node has been decreased.
+    unsafe fn decrease(&mut self, node: NonNull<Inner<K, P, D>>)
     where
         P: PartialOrd,
     {
-        if let Some(node) = targ {
-            match node.as_ref().parent {
-                Some(parent) => {
-                    let mut prev = parent.as_ref().left;
-
-                    while let Some(prev_node) = prev {
-                        if prev_node.as_ref().right == targ {
-                            break;
-                        } else {
-                            prev = prev_node.as_ref().right;
-                        }
-                    }
+        if let Some(parent) = node.as_ref().parent {
+            self.record(0, 1, 0);
+            if parent.as_ref().precedes(node.as_ref()) {
+                return;
+            }
 
-                    (*node.as_ptr()).prio = new_prio;
+            self.cut(node);
+            self.root = self.merge_nodes(self.root, Some(node));
+        }
+    }
 
-                    if parent.as_ref().prio < node.as_ref().prio {
-                        return;
-                    }
+    /// Detaches a non-root node, together with its children, from its parent and siblings.
+    unsafe fn cut(&mut self, node: NonNull<Inner<K, P, D>>) {
+        // Every non-root node must have a parent. So unwrap() here shouldn't panic.
+        let parent = (*node.as_ptr()).parent.take().unwrap();
+        let right = (*node.as_ptr()).right.take();
 
-                    if parent.as_ref().left == targ {
-                        (*parent.as_ptr()).left = node.as_ref().right;
-                    }
+        if parent.as_ref().left == Some(node) {
+            (*parent.as_ptr()).left = right;
+        } else {
+            let mut prev = parent.as_ref().left;
 
-                    if let Some(prev_node) = prev {
-                        if prev_node.as_ref().right == targ {
-                            (*prev_node.as_ptr()).right = node.as_ref().right;
-                        }
-                    }
+            while let Some(prev_node) = prev {
+                if prev_node.as_ref().right == Some(node) {
+                    (*prev_node.as_ptr()).right = right;
+                    break;
+                }
 
-                    (*node.as_ptr()).parent = None;
-                    (*node.as_ptr()).right = None;
+                prev = prev_node.as_ref().right;
+            }
+        }
+    }
 
-                    self.root = Self::merge_nodes(self.root, targ);
-                }
-                None => {
-                    (*node.as_ptr()).prio = new_prio;
+    /// Removes an arbitrary node from the heap and returns its key, priority and payload.
+    unsafe fn remove_node(&mut self, node: NonNull<Inner<K, P, D>>) -> (K, P, D)
+    where
+        P: PartialOrd,
+    {
+        let children = self.merge_pairs((*node.as_ptr()).left.take());
+
+        if node.as_ref().parent.is_some() {
+            self.cut(node);
+            self.root = self.merge_nodes(self.root, children);
+        } else {
+            self.root = children;
+        }
+
+        self.len -= 1;
+        Box::from_raw(node.as_ptr()).into_parts()
+    }
+
+    /// Combines a list of siblings into one tree using the standard two-pass pairing.
+    unsafe fn merge_pairs(
+        &mut self,
+        targ: Option<NonNull<Inner<K, P, D>>>,
+    ) -> Option<NonNull<Inner<K, P, D>>>
+    where
+        P: PartialOrd,
+    {
+        self.merge_pairs_by(targ, &ByPrio)
+    }
+
+    unsafe fn merge_pairs_by<O>(
+        &mut self,
+        mut targ: Option<NonNull<Inner<K, P, D>>>,
+        order: &O,
+    ) -> Option<NonNull<Inner<K, P, D>>>
+    where
+        O: Order<K, P>,
+    {
+        // TODO: optimise so that capacity is known here.
+        let mut tmp_nodes = VecDeque::new();
+
+        // First pass: left to right
+        while let Some(node) = targ {
+            (*node.as_ptr()).parent = None;
+            let right = (*node.as_ptr()).right.take();
+
+            let node_next = match right {
+                Some(node_right) => {
+                    let next = (*node_right.as_ptr()).right.take();
+                    (*node_right.as_ptr()).parent = None;
+                    next
                 }
+                None => None,
             };
+
+            tmp_nodes.push_back(self.merge_nodes_by(Some(node), right, order));
+
+            targ = node_next;
         }
+
+        // Second pass: right to left
+        let mut node = tmp_nodes.pop_back().flatten();
+
+        while let Some(node_prev) = tmp_nodes.pop_back() {
+            node = self.merge_nodes_by(node, node_prev, order);
+        }
+
+        node
     }
 
-    /// Deletes the minimum element, which is the root, of the heap, and then returns the root's key value and priority.
-    pub fn delete_min(&mut self) -> Option<(K, P)>
+    /// Combines a list of siblings into one tree by pairing them up from left to right in repeated passes.
+    unsafe fn merge_multipass(
+        &mut self,
+        targ: Option<NonNull<Inner<K, P, D>>>,
+    ) -> Option<NonNull<Inner<K, P, D>>>
     where
         P: PartialOrd,
+    {
+        self.merge_multipass_by(targ, &ByPrio)
+    }
+
+    unsafe fn merge_multipass_by<O>(
+        &mut self,
+        mut targ: Option<NonNull<Inner<K, P, D>>>,
+        order: &O,
+    ) -> Option<NonNull<Inner<K, P, D>>>
+    where
+        O: Order<K, P>,
+    {
+        let mut tmp_nodes = VecDeque::new();
+
+        while let Some(node) = targ {
+            (*node.as_ptr()).parent = None;
+            targ = (*node.as_ptr()).right.take();
+            tmp_nodes.push_back(node);
+        }
+
+        // Merging the two front trees and appending the result pairs up all trees of one pass before
+        // any tree of the next pass.
+        while tmp_nodes.len() > 1 {
+            let node = self.merge_nodes_by(tmp_nodes.pop_front(), tmp_nodes.pop_front(), order);
+            tmp_nodes.extend(node);
+        }
+
+        tmp_nodes.pop_front()
+    }
+
+    /// Rebuilds the heap's tree from scratch in ```O(n)``` time.
+    ///
+    /// Long sequences of priority changes can degrade the tree, e.g. into a long chain, which slows down
+    /// later operations. All nodes are unlinked and paired up again in repeated passes, like in a
+    /// tournament, so that the depth of the tree is ```O(log n)``` afterwards. The elements themselves
+    /// are not moved, so that handles to them stay valid.
+    pub fn rebuild(&mut self)
+    where
+        P: PartialOrd,
+    {
+        let mut siblings = None;
+
+        for node in self.nodes() {
+            unsafe {
+                (*node.as_ptr()).parent = None;
+                (*node.as_ptr()).left = None;
+                (*node.as_ptr()).right = siblings;
+            }
+            siblings = Some(node);
+        }
+
+        self.root = unsafe { self.merge_multipass(siblings) };
+    }
+
+    /// Removes all elements from the heap and returns them, in arbitrary order, as an iterator.
+    ///
+    /// The iterator yields the keys and priorities of the elements, their payloads are dropped.
+    /// The heap is empty after this call, even if the iterator is dropped before it is fully consumed.
+    /// Elements that are not yielded are dropped together with the iterator.
+    pub fn drain(&mut self) -> Drain<'_, K, P, D> {
+        let stack = self.root.take().into_iter().collect();
+        self.len = 0;
+
+        Drain {
+            stack,
+            marker: PhantomData,
+        }
+    }
+
+    /// Deletes the minimum element, which is the root, of the heap, and then returns the root's key value,
+    /// priority and payload.
+    pub fn delete_min_with(&mut self) -> Option<(K, P, D)>
+    where
+        P: PartialOrd,
+    {
+        self.delete_min_by(&ByPrio)
+    }
+
+    /// Deletes the root of a heap, whose elements are ordered by ```order```, and returns the root's key
+    /// value, priority and payload.
+    pub(crate) fn delete_min_by<O>(&mut self, order: &O) -> Option<(K, P, D)>
+    where
+        O: Order<K, P>,
     {
         self.root.map(|root| unsafe {
             self.len -= 1;
-            let mut targ = (*root.as_ptr()).left.take();
-            if targ.is_none() {
-                self.root = None;
-            } else {
-                // TODO: optimise so that capacity is known here.
-                let mut tmp_nodes = VecDeque::new();
-
-                // First pass: left to right
-                while let Some(node) = targ {
-                    (*node.as_ptr()).parent = None;
-                    let right = (*node.as_ptr()).right.take();
-
-                    let node_next = match right {
-                        Some(node_right) => {
-                            let next = (*node_right.as_ptr()).right.take();
-                            (*node_right.as_ptr()).parent = None;
-                            next
-                        }
-                        None => None,
-                    };
-
-                    tmp_nodes.push_back(Self::merge_nodes(Some(node), right));
-
-                    targ = node_next;
-                }
+            let targ = (*root.as_ptr()).left.take();
+            self.root = match self.delete_strategy {
+                DeleteStrategy::TwoPass => self.merge_pairs_by(targ, order),
+                DeleteStrategy::Multipass => self.merge_multipass_by(targ, order),
+            };
+            let node = Box::from_raw(root.as_ptr());
+            node.into_parts()
+        })
+    }
+}
+
+impl<K, P> PairingHeap<K, P> {
+    /// Creates a heap from an iterator of keys, whose priorities are computed by ```prio_fn```.
+    ///
+    /// All elements are first linked into a list and then paired up in one go, which takes ```O(n)```
+    /// time and balances the heap better than inserting the elements one by one.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::PairingHeap;
+    ///
+    /// let words = vec!["pairing", "heap", "a"];
+    /// let mut heap = PairingHeap::from_iter_by(words, |w| w.len());
+    ///
+    /// assert_eq!(Some(("a", 1)), heap.delete_min());
+    /// assert_eq!(Some(("heap", 4)), heap.delete_min());
+    /// ```
+    pub fn from_iter_by<I, F>(items: I, prio_fn: F) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        F: Fn(&K) -> P,
+        P: PartialOrd,
+    {
+        let mut heap = Self::new();
+        let mut siblings: Option<NonNull<Inner<K, P, ()>>> = None;
+
+        for key in items {
+            let prio = prio_fn(&key);
+            let mut inner = Inner::new(key, prio, ());
+            inner.right = siblings;
+            siblings = NonNull::new(Box::leak(Box::new(inner)));
+            heap.len += 1;
+        }
 
-                // Second pass: right to left
-                // If left is not None, there must be at least one element in VecDeque.
-                // So unwrap() is safe here.
-                let mut node = tmp_nodes.pop_back().unwrap();
+        heap.root = unsafe { heap.merge_pairs(siblings) };
+        heap
+    }
+
+    /// Creates a heap from pairs of keys and priorities, which must be sorted by priority in ascending
+    /// order. This is only checked in debug builds.
+    ///
+    /// Each element becomes the only child of its predecessor, so that the heap is built in ```O(n)```
+    /// time without any comparison, and every [`PairingHeap::delete_min`] takes ```O(1)``` time until
+    /// the heap is modified otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::from_sorted(vec![("a", 1), ("b", 2), ("c", 2)]);
+    ///
+    /// assert_eq!(3, heap.len());
+    /// assert_eq!(Some(("a", 1)), heap.delete_min());
+    /// ```
+    pub fn from_sorted(pairs: Vec<(K, P)>) -> Self
+    where
+        P: PartialOrd,
+    {
+        debug_assert!(
+            pairs.windows(2).all(|w| w[0].1 <= w[1].1),
+            "pairs must be sorted by priority in ascending order"
+        );
+
+        let mut heap = Self::new();
+        heap.len = pairs.len();
+        let mut parent: Option<NonNull<Inner<K, P, ()>>> = None;
+
+        for (key, prio) in pairs {
+            let mut inner = Inner::new(key, prio, ());
+            inner.parent = parent;
+            let node = NonNull::new(Box::leak(Box::new(inner)));
+
+            match parent {
+                Some(parent) => unsafe { (*parent.as_ptr()).left = node },
+                None => heap.root = node,
+            }
+
+            parent = node;
+        }
 
-                while let Some(node_prev) = tmp_nodes.pop_back() {
-                    node = Self::merge_nodes(node, node_prev);
+        heap
+    }
+
+    /// Inserts a new element to the heap.
+    #[inline]
+    pub fn insert(&mut self, key: K, prio: P)
+    where
+        P: PartialOrd,
+    {
+        self.insert2(key, prio);
+    }
+
+    // Expose HeapElmt to pub, no?
+    #[inline]
+    pub(crate) fn insert2(&mut self, key: K, prio: P) -> HeapElmt<K, P>
+    where
+        P: PartialOrd,
+    {
+        self.insert_node(key, prio, ())
+    }
+
+    /// Deletes the minimum element, which is the root, of the heap, and then returns the root's key value and priority.
+    ///
+    /// Returns ```None``` if the heap is empty, so that all elements can be processed in priority order
+    /// with a ```while let``` loop, without checking the length of the heap first:
+    /// ```
+    /// use pheap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// heap.insert("b", 2);
+    /// heap.insert("a", 1);
+    ///
+    /// let mut keys = Vec::new();
+    /// while let Some((key, _)) = heap.delete_min() {
+    ///     keys.push(key);
+    /// }
+    /// assert_eq!(vec!["a", "b"], keys);
+    /// ```
+    pub fn delete_min(&mut self) -> Option<(K, P)>
+    where
+        P: PartialOrd,
+    {
+        self.delete_min_with().map(|(key, prio, _)| (key, prio))
+    }
+
+    /// Replaces the minimum element with a new element and returns the key and priority of the old minimum.
+    ///
+    /// This is cheaper than [`PairingHeap::delete_min`] followed by [`PairingHeap::insert`]: the new
+    /// element joins the children of the old root, so that the new tree is built in a single pairing pass.
+    /// If the heap is empty, the element is just inserted and ```None``` is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// assert_eq!(None, heap.replace_min("a", 1));
+    /// heap.insert("b", 3);
+    ///
+    /// assert_eq!(Some(("a", 1)), heap.replace_min("c", 5));
+    /// assert_eq!(Some(("b", 3)), heap.delete_min());
+    /// assert_eq!(Some(("c", 5)), heap.delete_min());
+    /// ```
+    pub fn replace_min(&mut self, key: K, prio: P) -> Option<(K, P)>
+    where
+        P: PartialOrd,
+    {
+        let root = match self.root {
+            Some(root) => root,
+            None => {
+                self.insert(key, prio);
+                return None;
+            }
+        };
+
+        let node = self.new_node(key, prio, ());
+
+        unsafe {
+            (*node.as_ptr()).right = (*root.as_ptr()).left.take();
+            self.root = match self.delete_strategy {
+                DeleteStrategy::TwoPass => self.merge_pairs(Some(node)),
+                DeleteStrategy::Multipass => self.merge_multipass(Some(node)),
+            };
+
+            let (key, prio, _) = Box::from_raw(root.as_ptr()).into_parts();
+            Some((key, prio))
+        }
+    }
+
+    /// Inserts a new element and then removes the minimum element, returning its key and priority.
+    ///
+    /// If the new element would be removed right away, i.e. its priority is less than or equal to the
+    /// current minimum, it is returned without touching the heap. Otherwise, this behaves like
+    /// [`PairingHeap::replace_min`].
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// assert_eq!(("a", 1), heap.push_pop("a", 1));
+    /// heap.insert("b", 3);
+    ///
+    /// assert_eq!(("c", 2), heap.push_pop("c", 2));
+    /// assert_eq!(("b", 3), heap.push_pop("d", 5));
+    /// assert_eq!(Some(("d", 5)), heap.delete_min());
+    /// ```
+    pub fn push_pop(&mut self, key: K, prio: P) -> (K, P)
+    where
+        P: PartialOrd,
+    {
+        if let Some(root) = self.root {
+            self.record(0, 1, 0);
+            let root_prio = unsafe { &root.as_ref().prio };
+            let precedes = matches!(
+                prio.partial_cmp(root_prio),
+                Some(Ordering::Less) | Some(Ordering::Equal)
+            );
+
+            if !precedes {
+                return self.replace_min(key, prio).unwrap();
+            }
+        }
+
+        (key, prio)
+    }
+
+    /// Removes a random element from the heap, which is selected with a probability inversely proportional
+    /// to its priority, and returns its key and priority.
+    ///
+    /// Priorities must be positive. Elements whose priority is not positive or can't be converted to
+    /// ```f64``` are never selected. If no element can be selected, the minimum element is removed.
+    ///
+    /// All elements must be visited to compute the probabilities, so this takes ```O(n)``` time, plus
+    /// the time to restore the heap after the removal, which is ```O(log n)``` amortized as for
+    /// [`PairingHeap::delete_min`].
+    ///
+    /// This function is only available with the ```rand``` feature.
+    #[cfg(feature = "rand")]
+    pub fn sample_pop<R>(&mut self, rng: &mut R) -> Option<(K, P)>
+    where
+        R: rand::Rng + ?Sized,
+        P: PartialOrd + ToPrimitive,
+    {
+        let nodes = self.nodes();
+        let weights: Vec<f64> = nodes
+            .iter()
+            .map(|node| match unsafe { node.as_ref() }.prio.to_f64() {
+                Some(prio) if prio > 0.0 && prio.is_finite() => 1.0 / prio,
+                _ => 0.0,
+            })
+            .collect();
+
+        let total: f64 = weights.iter().sum();
+        if !(total > 0.0 && total.is_finite()) {
+            return self.delete_min();
+        }
+
+        let mut target = rng.gen_range(0.0..total);
+        let mut selected = None;
+
+        for (node, weight) in nodes.iter().zip(&weights) {
+            if *weight > 0.0 {
+                selected = Some(*node);
+                if target < *weight {
+                    break;
                 }
+                target -= weight;
+            }
+        }
+
+        // The last candidate is selected, if rounding errors leave a rest of the target.
+        selected.map(|node| {
+            let (key, prio, _) = unsafe { self.remove_node(node) };
+            (key, prio)
+        })
+    }
 
-                self.root = node;
+    /// Deletes all elements whose priority equals the minimum priority and returns them in the order in
+    /// which they were removed. Returns an empty vector if the heap is empty.
+    ///
+    /// The order among the removed elements depends on the structure of the heap. Use
+    /// [`StablePairingHeap`](crate::StablePairingHeap) to remove them in insertion order.
+    ///
+    /// # Examples
+    /// ```
+    /// use pheap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// heap.insert("b", 1);
+    /// heap.insert("c", 2);
+    /// heap.insert("a", 1);
+    ///
+    /// let mut batch = heap.delete_all_min();
+    /// batch.sort();
+    /// assert_eq!(vec![("a", 1), ("b", 1)], batch);
+    /// assert_eq!(1, heap.len());
+    /// ```
+    pub fn delete_all_min(&mut self) -> Vec<(K, P)>
+    where
+        P: PartialOrd,
+    {
+        let mut batch = Vec::new();
+
+        if let Some(first) = self.delete_min() {
+            batch.push(first);
+
+            while matches!(self.find_min(), Some((_, prio)) if *prio == batch[0].1) {
+                batch.extend(self.delete_min());
             }
-            let node = Box::from_raw(root.as_ptr());
+        }
+
+        batch
+    }
+}
+
+/// A draining iterator over the elements of a [`PairingHeap`].
+///
+/// This struct is created by [`PairingHeap::drain`].
+#[derive(Debug)]
+pub struct Drain<'a, K, P, D = ()> {
+    stack: Vec<NonNull<Inner<K, P, D>>>,
+    marker: PhantomData<&'a mut PairingHeap<K, P, D>>,
+}
+
+impl<'a, K, P, D> Iterator for Drain<'a, K, P, D> {
+    type Item = (K, P);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stack.pop().map(|node| unsafe {
+            let node = Box::from_raw(node.as_ptr());
+
+            if let Some(right) = node.right {
+                self.stack.push(right);
+            }
+
+            if let Some(left) = node.left {
+                self.stack.push(left);
+            }
+
             node.into_value()
         })
     }
 }
 
-impl<K, P> Default for PairingHeap<K, P> {
-    fn default() -> Self {
-        Self { root: None, len: 0 }
+impl<'a, K, P, D> Drop for Drain<'a, K, P, D> {
+    fn drop(&mut self) {
+        self.for_each(drop);
     }
 }
 
-impl<K, P> Drop for PairingHeap<K, P> {
-    fn drop(&mut self) {
-        // Remove all children of a node, then the node itself.
-        // Returns the next sibling in the end.
+/// A min-pairing heap whose elements are their own priorities.
+///
+/// The key of each element is the zero-sized ```()```, so that a value is stored only once.
+pub type ValueHeap<T> = PairingHeap<(), T>;
 
-        unsafe fn remove<K, P>(targ: Option<NonNull<Inner<K, P>>>) -> Option<NonNull<Inner<K, P>>> {
-            if let Some(node) = targ {
-                while let Some(left) = node.as_ref().left {
-                    (*node.as_ptr()).left = remove(Some(left));
-                }
+impl<T> PairingHeap<(), T> {
+    /// Inserts a new value to the heap, which is also its priority.
+    #[inline]
+    pub fn insert_value(&mut self, value: T)
+    where
+        T: PartialOrd,
+    {
+        self.insert((), value);
+    }
 
-                let sibling = (*node.as_ptr()).right.take();
-                (*node.as_ptr()).parent = None;
-                Box::from_raw(node.as_ptr());
+    /// Returns the minimum value of the heap.
+    #[inline]
+    pub fn find_min_value(&self) -> Option<&T> {
+        self.find_min().map(|(_, value)| value)
+    }
 
-                sibling
-            } else {
-                None
-            }
+    /// Deletes the minimum value of the heap and returns it.
+    #[inline]
+    pub fn delete_min_value(&mut self) -> Option<T>
+    where
+        T: PartialOrd,
+    {
+        self.delete_min().map(|(_, value)| value)
+    }
+}
+
+impl<K, P, D> Default for PairingHeap<K, P, D> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            delete_strategy: DeleteStrategy::TwoPass,
+            #[cfg(feature = "instrument")]
+            counts: None,
         }
+    }
+}
 
-        unsafe {
-            remove(self.root);
+/// Converts a heap of the standard library into a pairing heap, whose keys and priorities are the values.
+///
+/// [`BinaryHeap`] is a max-heap, while [`PairingHeap`] is a min-heap. The values are moved across unchanged,
+/// so the pairing heap returns them in ascending order, i.e. in the reverse order of the binary heap. To keep
+/// the order, convert into a [`MaxPairingHeap`](crate::MaxPairingHeap) instead, or convert a min-heap
+/// ```BinaryHeap<Reverse<T>>```. This takes ```O(n)``` time.
+///
+/// # Examples
+/// ```
+/// use pheap::PairingHeap;
+/// use std::collections::BinaryHeap;
+///
+/// let bh: BinaryHeap<_> = vec![2, 3, 1].into_iter().collect();
+/// assert_eq!(Some(&3), bh.peek());
+///
+/// let ph = PairingHeap::<i32, i32>::from(bh);
+/// assert_eq!(Some((&1, &1)), ph.find_min());
+/// ```
+impl<T> From<BinaryHeap<T>> for PairingHeap<T, T>
+where
+    T: Ord + Clone,
+{
+    fn from(heap: BinaryHeap<T>) -> Self {
+        Self::from_iter_by(heap, |value| value.clone())
+    }
+}
+
+/// Converts a pairing heap, whose keys and priorities are the same values, into a heap of the standard library.
+///
+/// The values are moved across unchanged, so the max-heap [`BinaryHeap`] returns them in descending order, i.e.
+/// in the reverse order of the pairing heap. Only heaps whose keys are their priorities can be converted, so
+/// that no key is lost. This takes ```O(n)``` time.
+impl<T> From<PairingHeap<T, T>> for BinaryHeap<T>
+where
+    T: Ord,
+{
+    fn from(mut heap: PairingHeap<T, T>) -> Self {
+        heap.drain().map(|(value, _)| value).collect()
+    }
+}
+
+/// Converts a min-heap of the standard library into a pairing heap, whose keys and priorities are the values.
+///
+/// A min-heap is a ```BinaryHeap<Reverse<T>>```, which returns the values in the same order as the pairing heap.
+/// This takes ```O(n)``` time.
+impl<T> From<BinaryHeap<Reverse<T>>> for PairingHeap<T, T>
+where
+    T: Ord + Clone,
+{
+    fn from(heap: BinaryHeap<Reverse<T>>) -> Self {
+        let values = heap.into_iter().map(|Reverse(value)| value);
+        Self::from_iter_by(values, |value| value.clone())
+    }
+}
+
+/// Converts a pairing heap, whose keys and priorities are the same values, into a min-heap of the standard
+/// library, which returns the values in the same order. This takes ```O(n)``` time.
+impl<T> From<PairingHeap<T, T>> for BinaryHeap<Reverse<T>>
+where
+    T: Ord,
+{
+    fn from(mut heap: PairingHeap<T, T>) -> Self {
+        heap.drain().map(|(value, _)| Reverse(value)).collect()
+    }
+}
+
+/// Two heaps are equal if they contain the same multiset of elements, regardless of their internal structure.
+///
+/// The comparison sorts the elements of both heaps and thus takes ```O(n log n)``` time.
+impl<K, P> PartialEq for PairingHeap<K, P>
+where
+    K: Ord,
+    P: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.sorted_elements() == other.sorted_elements()
+    }
+}
+
+impl<K, P> Eq for PairingHeap<K, P>
+where
+    K: Ord,
+    P: Ord,
+{
+}
+
+/// The hash is computed from the sorted multiset of elements, so that it is consistent with [`PartialEq`].
+///
+/// Hashing sorts the elements of the heap and thus takes ```O(n log n)``` time.
+impl<K, P> Hash for PairingHeap<K, P>
+where
+    K: Hash + Ord,
+    P: Hash + Ord,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for (key, prio) in self.sorted_elements() {
+            key.hash(state);
+            prio.hash(state);
         }
+    }
+}
 
-        self.root = None;
+impl<K, P, D> Drop for PairingHeap<K, P, D> {
+    fn drop(&mut self) {
+        // The draining iterator frees the nodes with an explicit stack, so that deep trees, such as the
+        // chain built by from_sorted, don't overflow the call stack.
+        self.drain();
+    }
+}
+
+/// The order of the elements of a heap, which decides the root whenever two trees are melded.
+pub(crate) trait Order<K, P> {
+    /// Checks whether the first element must be placed above the second element in the heap.
+    fn precedes(&self, key1: &K, prio1: &P, key2: &K, prio2: &P) -> bool;
+}
+
+/// Orders the elements by their priorities, which is the order of every heap with the public API.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ByPrio;
+
+impl<K, P: PartialOrd> Order<K, P> for ByPrio {
+    #[inline(always)]
+    fn precedes(&self, _: &K, prio1: &P, _: &K, prio2: &P) -> bool {
+        prio1 < prio2
     }
 }
 
 #[derive(Clone, Debug)]
-pub(crate) struct HeapElmt<K, P> {
-    inner: Option<NonNull<Inner<K, P>>>,
+pub(crate) struct HeapElmt<K, P, D = ()> {
+    inner: Option<NonNull<Inner<K, P, D>>>,
 }
 
-impl<K, P> HeapElmt<K, P> {
+impl<K, P, D> HeapElmt<K, P, D> {
     pub(crate) fn is_none(&self) -> bool {
         self.inner.is_none()
     }
@@ -336,36 +1484,51 @@ impl<K, P> HeapElmt<K, P> {
     }
 }
 
-impl<K, P> Default for HeapElmt<K, P> {
+impl<K, P, D> Default for HeapElmt<K, P, D> {
     fn default() -> Self {
         Self { inner: None }
     }
 }
 
 #[derive(Debug)]
-struct Inner<K, P> {
+struct Inner<K, P, D> {
     /// Pointer to a node's parent.
-    parent: Option<NonNull<Inner<K, P>>>,
+    parent: Option<NonNull<Inner<K, P, D>>>,
     /// Pointer to a node's first (or left-most) child.
-    left: Option<NonNull<Inner<K, P>>>,
+    left: Option<NonNull<Inner<K, P, D>>>,
     /// Pointer to a node's next older sibling.
-    right: Option<NonNull<Inner<K, P>>>,
+    right: Option<NonNull<Inner<K, P, D>>>,
     key: K,
     prio: P,
+    data: D,
 }
 
-impl<K, P> Inner<K, P> {
-    fn new(key: K, prio: P) -> Self {
+impl<K, P, D> Inner<K, P, D> {
+    fn new(key: K, prio: P, data: D) -> Self {
         Self {
             key,
             prio,
+            data,
             parent: None,
             left: None,
             right: None,
         }
     }
 
+    /// Checks whether a node must be placed above another node in the heap.
+    #[inline(always)]
+    fn precedes(&self, other: &Self) -> bool
+    where
+        P: PartialOrd,
+    {
+        self.prio < other.prio
+    }
+
     fn into_value(self) -> (K, P) {
         (self.key, self.prio)
     }
+
+    fn into_parts(self) -> (K, P, D) {
+        (self.key, self.prio, self.data)
+    }
 }
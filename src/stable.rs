@@ -0,0 +1,236 @@
+use std::{borrow::Borrow, cmp::Ordering, ops::SubAssign};
+
+use crate::{ph::HeapElmt, PairingHeap};
+
+/// A min-pairing heap, which breaks ties between equal priorities by insertion order.
+///
+/// Among elements with the same priority, [`StablePairingHeap::delete_min`] returns the element inserted
+/// first (FIFO), so that the output of a replayed sequence of operations is deterministic. Changing the
+/// priority of an element keeps its original position in the insertion order.
+///
+/// The heap is a [`PairingHeap`] whose priorities are stamped with an insertion sequence number. This costs
+/// one ```u64``` per element and an additional comparison whenever two priorities are equal, which plain
+/// pairing heaps don't pay.
+///
+/// # Examples
+/// ```
+/// use pheap::StablePairingHeap;
+///
+/// let mut heap = StablePairingHeap::new_stable();
+/// heap.insert("b", 1);
+/// heap.insert("c", 2);
+/// heap.insert("a", 1);
+///
+/// assert_eq!(vec![("b", 1), ("a", 1)], heap.delete_all_min());
+/// assert_eq!(1, heap.len());
+/// ```
+#[derive(Debug)]
+pub struct StablePairingHeap<K, P> {
+    heap: PairingHeap<K, Stamped<P>>,
+    /// Insertion sequence number of the last inserted element.
+    seq: u64,
+}
+
+impl<K, P> StablePairingHeap<K, P> {
+    /// Creates an empty stable pairing heap.
+    #[inline]
+    pub fn new_stable() -> Self {
+        Self {
+            heap: PairingHeap::new(),
+            seq: 0,
+        }
+    }
+
+    /// Returns the number of elements stored in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks whether the heap is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the element with the minimum priority, which was inserted first among all such elements.
+    #[inline]
+    pub fn find_min(&self) -> Option<(&K, &P)> {
+        self.heap.find_min().map(|(key, prio)| (key, &prio.prio))
+    }
+
+    /// Merges two heaps together and forms a new heap.
+    ///
+    /// Elements of the same heap keep their relative insertion order, while ties between elements of
+    /// different heaps are broken by their sequence numbers.
+    pub fn merge(self, other: Self) -> Self
+    where
+        P: PartialOrd,
+    {
+        Self {
+            heap: self.heap.merge(other.heap),
+            seq: self.seq.max(other.seq),
+        }
+    }
+
+    /// Inserts a new element to the heap.
+    #[inline]
+    pub fn insert(&mut self, key: K, prio: P)
+    where
+        P: PartialOrd,
+    {
+        self.insert2(key, prio);
+    }
+
+    #[inline]
+    pub(crate) fn insert2(&mut self, key: K, prio: P) -> HeapElmt<K, Stamped<P>>
+    where
+        P: PartialOrd,
+    {
+        let prio = self.stamp(prio);
+        self.heap.insert2(key, prio)
+    }
+
+    /// Changes the priority of the element referenced by a handle, which keeps its position in the
+    /// insertion order.
+    pub(crate) fn update_prio(&mut self, elmt: &HeapElmt<K, Stamped<P>>, new_prio: P)
+    where
+        P: PartialOrd,
+    {
+        if let Some(seq) = self.heap.prio(elmt).map(|prio| prio.seq) {
+            self.heap.update_prio(
+                elmt,
+                Stamped {
+                    prio: new_prio,
+                    seq,
+                },
+            );
+        }
+    }
+
+    /// Decreases the priority of a key by the amount given in ```delta``` and returns its previous
+    /// priority, or ```None``` if the key is not found in the heap.
+    pub fn decrease_prio<Q>(&mut self, key: &Q, delta: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+        P: PartialOrd + SubAssign + Clone,
+    {
+        self.heap
+            .map_prio_by_key(key, |old| {
+                let mut prio = old.prio.clone();
+                prio -= delta;
+                Stamped { prio, seq: old.seq }
+            })
+            .map(|old| old.prio)
+    }
+
+    /// Changes the priority of a key to ```new_prio``` and returns its previous priority, or ```None```
+    /// if the key is not found in the heap.
+    pub fn change_prio_by_key<Q>(&mut self, key: &Q, new_prio: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+        P: PartialOrd,
+    {
+        self.heap
+            .map_prio_by_key(key, |old| Stamped {
+                prio: new_prio,
+                seq: old.seq,
+            })
+            .map(|old| old.prio)
+    }
+
+    /// Deletes the element with the minimum priority, which was inserted first among all such elements,
+    /// and returns it.
+    #[inline]
+    pub fn delete_min(&mut self) -> Option<(K, P)>
+    where
+        P: PartialOrd,
+    {
+        self.heap.delete_min().map(|(key, prio)| (key, prio.prio))
+    }
+
+    /// Deletes all elements whose priority equals the minimum priority and returns them in insertion
+    /// order. Returns an empty vector if the heap is empty.
+    pub fn delete_all_min(&mut self) -> Vec<(K, P)>
+    where
+        P: PartialOrd,
+    {
+        let mut batch: Vec<(K, P)> = Vec::new();
+
+        while let Some((_, prio)) = self.find_min() {
+            if batch.first().is_some_and(|(_, first)| first != prio) {
+                break;
+            }
+            batch.extend(self.delete_min());
+        }
+
+        batch
+    }
+
+    /// Deletes the element with the minimum priority and inserts a new element in one step. Returns the
+    /// deleted element, or ```None``` if the heap was empty.
+    ///
+    /// See [`PairingHeap::replace_min`].
+    pub fn replace_min(&mut self, key: K, prio: P) -> Option<(K, P)>
+    where
+        P: PartialOrd,
+    {
+        let prio = self.stamp(prio);
+        self.heap
+            .replace_min(key, prio)
+            .map(|(key, prio)| (key, prio.prio))
+    }
+
+    /// Inserts a new element and then deletes the element with the minimum priority in one step.
+    ///
+    /// The new element loses ties because it was inserted last, so it is only returned right away if its
+    /// priority is strictly less than the current minimum. See [`PairingHeap::push_pop`].
+    pub fn push_pop(&mut self, key: K, prio: P) -> (K, P)
+    where
+        P: PartialOrd,
+    {
+        let prio = self.stamp(prio);
+        let (key, prio) = self.heap.push_pop(key, prio);
+        (key, prio.prio)
+    }
+
+    /// Stamps a priority with the next sequence number.
+    #[inline]
+    fn stamp(&mut self, prio: P) -> Stamped<P> {
+        self.seq += 1;
+        Stamped {
+            prio,
+            seq: self.seq,
+        }
+    }
+}
+
+impl<K, P> Default for StablePairingHeap<K, P> {
+    fn default() -> Self {
+        Self::new_stable()
+    }
+}
+
+/// A priority stamped with the insertion sequence number of its element, which breaks ties.
+#[derive(Clone, Debug)]
+pub(crate) struct Stamped<P> {
+    prio: P,
+    seq: u64,
+}
+
+impl<P: PartialEq> PartialEq for Stamped<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.prio == other.prio && self.seq == other.seq
+    }
+}
+
+impl<P: PartialOrd> PartialOrd for Stamped<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.prio.partial_cmp(&other.prio) {
+            Some(Ordering::Equal) => Some(self.seq.cmp(&other.seq)),
+            ord => ord,
+        }
+    }
+}
@@ -1,6 +1,10 @@
 #![cfg(test)]
-use super::PairingHeap;
-use crate::graph::{mst_prim, SimpleGraph};
+use super::{DeleteStrategy, MaxPairingHeap, PairingHeap, StablePairingHeap};
+use crate::graph::{
+    alt::AltPreprocessed, grid_index, mst_forest, mst_prim, mst_prim_edges, mst_prim_visit,
+    try_mst_prim, DijkstraBuffer, DijkstraStrategy, DijkstraVisitor, GraphError, LabeledGraph,
+    NegativeCycle, NotADag, PrimVisitor, SimpleGraph,
+};
 use crate::ph::HeapElmt;
 
 #[cfg(test)]
@@ -23,6 +27,29 @@ fn create_insert() {
     assert_eq!(10, ph.len());
 }
 
+#[test]
+fn create_ord() {
+    let mut ph = PairingHeap::<char, u32>::new_ord();
+    ph.insert('b', 2);
+    ph.insert('a', 1);
+    ph.insert('c', 3);
+
+    assert_eq!(3, ph.len());
+    assert_eq!(Some((&'a', &1)), ph.find_min());
+}
+
+#[test]
+fn memory_estimate() {
+    let (ph, _) = create_heap(0, 0);
+    let empty = ph.memory_estimate();
+    assert!(empty > 0);
+
+    let (ph, _) = create_heap(0, 100);
+    let node_size = (ph.memory_estimate() - empty) / 100;
+    // Three pointers plus the key and the priority.
+    assert!(node_size >= 3 * std::mem::size_of::<usize>() + 2 * std::mem::size_of::<i32>());
+}
+
 #[test]
 fn find_min() {
     let (ph, _) = create_heap(0, 0);
@@ -47,6 +74,13 @@ fn merge() {
     let len1 = ph1.len();
     let ph2 = create_heap(11, 21).0;
     let len2 = ph2.len();
+    assert!(ph1.is_disjoint(&ph2));
+
+    // A shallow copy shares all nodes with the original heap, so the two must never be merged.
+    let copy = unsafe { std::ptr::read(&ph1) };
+    assert!(!ph1.is_disjoint(&copy));
+    assert!(ph1.is_disjoint(&PairingHeap::new()));
+    std::mem::forget(copy);
 
     let ph = ph2.merge(ph1);
     println!("Len: {}", ph.len());
@@ -58,6 +92,25 @@ fn merge() {
     assert_eq!(1, *p);
 }
 
+#[test]
+fn absorb() {
+    let mut ph = create_heap(11, 21).0;
+    let other = create_heap(1, 11).0;
+    assert!(ph.is_disjoint(&other));
+    ph.absorb(other);
+    ph.absorb(PairingHeap::new());
+    assert_eq!(20, ph.len());
+
+    let mut empty = PairingHeap::new();
+    empty.absorb(ph);
+    assert_eq!(20, empty.len());
+
+    for ii in 1..21 {
+        assert_eq!(Some((ii, ii)), empty.delete_min());
+    }
+    assert!(empty.is_empty());
+}
+
 #[test]
 fn delete_min() {
     let (mut ph, _) = create_heap(1, 11);
@@ -159,6 +212,7 @@ fn update_prio() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_dijkstra() {
     let mut g = SimpleGraph::<u32>::with_capacity(6);
 
@@ -178,7 +232,7 @@ fn test_dijkstra() {
     let sp = sp.pop().unwrap();
     assert_eq!(true, sp.is_feasible());
     assert_eq!(20, sp.dist());
-    assert_eq!(&[0, 2, 5, 4], sp.path().as_slice());
+    assert_eq!(&[0, 2, 5, 4], sp.path());
 
     g.add_weighted_edges(6, 7, 2);
     g.add_weighted_edges(6, 8, 3);
@@ -190,7 +244,23 @@ fn test_dijkstra() {
     let sp = lsp.get(4);
     assert_eq!(true, sp.is_feasible());
     assert_eq!(20, sp.dist());
-    assert_eq!(&[0, 2, 5, 4], sp.path().as_slice());
+    assert_eq!(&[0, 2, 5, 4], sp.path());
+
+    let pred = lsp.predecessors();
+    assert_eq!(
+        vec![
+            None,
+            Some(0),
+            Some(0),
+            Some(2),
+            Some(5),
+            Some(2),
+            None,
+            None,
+            None
+        ],
+        pred
+    );
 }
 
 #[test]
@@ -219,3 +289,2919 @@ fn test_mst_prim() {
     assert_eq!(g0.n_nodes(), g4.n_nodes());
     assert_eq!(g0.n_edges(), g4.n_edges());
 }
+
+#[test]
+fn change_prio() {
+    let (mut ph, v) = create_heap(1, 11);
+
+    assert_eq!(Some(8), ph.change_prio(&v[7], 0));
+    assert_eq!(Some(1), ph.change_prio(&v[0], 20));
+    assert_eq!(Some(5), ph.change_prio_by_key(&5, 15));
+    assert_eq!(Some(3), ph.change_prio_by_key(&3, 2));
+    assert_eq!(None, ph.change_prio_by_key(&42, 0));
+    assert_eq!(None, ph.change_prio(&HeapElmt::default(), 0));
+
+    let key_exp = vec![8, 2, 3, 4, 6, 7, 9, 10, 5, 1];
+    let prio_exp = vec![0, 2, 2, 4, 6, 7, 9, 10, 15, 20];
+
+    for (k, p) in key_exp.into_iter().zip(prio_exp) {
+        assert_eq!(Some((k, p)), ph.delete_min());
+    }
+
+    assert!(ph.is_empty());
+}
+
+#[test]
+fn borrowed_key() {
+    let mut ph = PairingHeap::<String, i32>::new();
+    for (ii, name) in ["a", "b", "c", "d"].iter().enumerate() {
+        ph.insert(name.to_string(), ii as i32 * 10);
+    }
+
+    assert_eq!(Some(20), ph.decrease_prio("c", 25));
+    assert_eq!(None, ph.decrease_prio("e", 25));
+    assert_eq!(Some(30), ph.change_prio_by_key("d", 1));
+    assert_eq!(None, ph.change_prio_by_key("e", 1));
+
+    assert_eq!(Some(("c".to_string(), -5)), ph.delete_min());
+    assert_eq!(Some(("a".to_string(), 0)), ph.delete_min());
+    assert_eq!(Some(("d".to_string(), 1)), ph.delete_min());
+}
+
+#[test]
+fn edge_queries() {
+    let mut g = SimpleGraph::<u32>::new();
+    g.add_weighted_edges(0, 1, 7);
+    g.add_weighted_edges(1, 2, 3);
+    g.add_weighted_arc(1, 2, 5);
+    g.add_weighted_arc(2, 1, 5);
+
+    assert!(g.has_edge(0, 1));
+    assert!(g.has_edge(1, 0));
+    assert_eq!(Some(&7), g.edge_weight(1, 0));
+    assert_eq!(Some(&3), g.edge_weight(2, 1));
+
+    assert!(!g.has_edge(0, 2));
+    assert_eq!(None, g.edge_weight(0, 2));
+
+    assert!(!g.has_edge(5, 0));
+    assert!(!g.has_edge(0, 5));
+    assert_eq!(None, g.edge_weight(5, 6));
+
+    assert!(g.has_node(2));
+    assert!(!g.has_node(3));
+    g.add_node(3);
+    assert!(g.has_node(3));
+}
+
+#[test]
+fn drain() {
+    let (mut ph, _) = create_heap(1, 11);
+
+    let mut drained: Vec<_> = ph.drain().collect();
+    drained.sort_unstable();
+    assert_eq!((1..11).map(|ii| (ii, ii)).collect::<Vec<_>>(), drained);
+    assert!(ph.is_empty());
+    assert!(ph.find_min().is_none());
+
+    for ii in 1..11 {
+        ph.insert(ii, ii);
+    }
+
+    let mut drain = ph.drain();
+    assert!(drain.next().is_some());
+    drop(drain);
+    assert_eq!(0, ph.len());
+    assert!(ph.delete_min().is_none());
+
+    ph.insert(3, 3);
+    assert_eq!(Some((3, 3)), ph.delete_min());
+}
+
+#[test]
+fn neighbours() {
+    let mut g = SimpleGraph::<u32>::new();
+    g.add_weighted_edges(0, 1, 7);
+    g.add_weighted_edges(0, 2, 9);
+
+    let nb: Vec<_> = g.neighbours(0).collect();
+    assert_eq!(vec![(1, &7), (2, &9)], nb);
+    assert_eq!(vec![(0, &9)], g.neighbours(2).collect::<Vec<_>>());
+    assert_eq!(0, g.neighbours(3).count());
+}
+
+#[test]
+fn to_csr() {
+    let mut g = SimpleGraph::<u32>::new();
+    g.add_weighted_edges(0, 1, 7);
+    g.add_weighted_edges(0, 3, 9);
+    g.add_weighted_edges(1, 3, 2);
+
+    let (indptr, indices, data) = g.to_csr();
+    assert_eq!(vec![0, 2, 4, 4, 6], indptr);
+    assert_eq!(vec![1, 3, 0, 3, 0, 1], indices);
+    assert_eq!(vec![7, 9, 7, 2, 9, 2], data);
+
+    let (indptr, indices, data) = SimpleGraph::<u32>::new().to_csr();
+    assert_eq!(vec![0], indptr);
+    assert!(indices.is_empty());
+    assert!(data.is_empty());
+}
+
+#[test]
+fn degree() {
+    let mut g = SimpleGraph::<u32>::new();
+    assert_eq!(0, g.max_degree());
+    assert_eq!(0.0, g.mean_degree());
+    assert!(g.degree_histogram().is_empty());
+
+    g.add_weighted_edges(0, 1, 1);
+    g.add_weighted_edges(0, 2, 1);
+    g.add_weighted_edges(0, 3, 1);
+    g.add_weighted_edges(1, 2, 1);
+
+    assert_eq!(3, g.degree(0));
+    assert_eq!(2, g.degree(1));
+    assert_eq!(1, g.degree(3));
+    assert_eq!(0, g.degree(4));
+    assert_eq!(3, g.max_degree());
+    assert_eq!(2.0, g.mean_degree());
+    assert_eq!(vec![(1, 1), (2, 2), (3, 1)], g.degree_histogram());
+}
+
+#[test]
+fn read_edgelist() {
+    let mut g = SimpleGraph::<u32>::new();
+    g.add_weighted_edges(0, 1, 7);
+    g.add_weighted_edges(0, 2, 9);
+    g.add_weighted_edges(1, 2, 10);
+
+    let dir = std::env::temp_dir().join(format!("pheap_edgelist_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("roundtrip.edgelist");
+    g.write_edgelist(&path).unwrap();
+    let g2 = SimpleGraph::<u32>::read_edgelist(&path).unwrap();
+
+    assert_eq!(g.n_nodes(), g2.n_nodes());
+    assert_eq!(g.n_edges(), g2.n_edges());
+    for node in 0..3 {
+        let mut nb1: Vec<_> = g.neighbours(node).collect();
+        let mut nb2: Vec<_> = g2.neighbours(node).collect();
+        nb1.sort_unstable();
+        nb2.sort_unstable();
+        assert_eq!(nb1, nb2);
+    }
+
+    let path = dir.join("commented.edgelist");
+    std::fs::write(
+        &path,
+        "# a comment\n\n0 1 {'weight': 3}\n1  0 {\"weight\":3, 'color': 'red'} # trailing\n",
+    )
+    .unwrap();
+    let g3 = SimpleGraph::<u32>::read_edgelist(&path).unwrap();
+    assert_eq!(Some(&3), g3.edge_weight(0, 1));
+    assert_eq!(Some(&3), g3.edge_weight(1, 0));
+
+    let path = dir.join("invalid.edgelist");
+    std::fs::write(&path, "0 1 {'color': 'red'}\n").unwrap();
+    let err = SimpleGraph::<u32>::read_edgelist(&path).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+
+    // NaN weights are rejected like when an edge is added.
+    let path = dir.join("nan.edgelist");
+    std::fs::write(&path, "0 1 {'weight': 2.5}\n1 2 {'weight': nan}\n").unwrap();
+    let err = SimpleGraph::<f64>::read_edgelist(&path).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    assert!(err.to_string().contains("line 2"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_mst_prim_edges() {
+    let mut g = SimpleGraph::<u32>::new();
+
+    g.add_weighted_edges(0, 1, 4);
+    g.add_weighted_edges(0, 7, 8);
+    g.add_weighted_edges(1, 2, 8);
+    g.add_weighted_edges(1, 7, 11);
+    g.add_weighted_edges(2, 3, 7);
+    g.add_weighted_edges(2, 5, 4);
+    g.add_weighted_edges(2, 8, 2);
+    g.add_weighted_edges(3, 4, 9);
+    g.add_weighted_edges(3, 5, 14);
+    g.add_weighted_edges(4, 5, 10);
+    g.add_weighted_edges(5, 6, 2);
+    g.add_weighted_edges(6, 7, 1);
+    g.add_weighted_edges(6, 8, 6);
+    g.add_weighted_edges(7, 8, 7);
+
+    let (mst, d) = mst_prim(&g, 0);
+    let (edges, de) = mst_prim_edges(&g, 0);
+
+    assert_eq!(37, de);
+    assert_eq!(d, de);
+    assert_eq!(
+        vec![
+            (0, 1, 4),
+            (1, 2, 8),
+            (2, 3, 7),
+            (3, 4, 9),
+            (2, 5, 4),
+            (5, 6, 2),
+            (6, 7, 1),
+            (2, 8, 2)
+        ],
+        edges
+    );
+    assert_eq!(g.n_nodes() - 1, edges.len());
+    for (p, c, w) in edges {
+        assert_eq!(Some(&w), mst.edge_weight(p, c));
+    }
+}
+
+#[test]
+fn test_mst_prim_visit() {
+    struct Order(Vec<(usize, usize, u32)>);
+
+    impl PrimVisitor<u32> for Order {
+        fn on_edge_added(&mut self, parent: usize, child: usize, weight: u32) {
+            self.0.push((parent, child, weight));
+        }
+    }
+
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 4),
+        (0, 7, 8),
+        (1, 2, 8),
+        (1, 7, 11),
+        (2, 3, 7),
+        (2, 5, 4),
+        (2, 8, 2),
+        (3, 4, 9),
+        (3, 5, 14),
+        (4, 5, 10),
+        (5, 6, 2),
+        (6, 7, 1),
+        (6, 8, 6),
+        (7, 8, 7),
+    ]);
+
+    let mut order = Order(Vec::new());
+    assert_eq!(37, mst_prim_visit(&g, 0, &mut order));
+
+    // Edges are reported in the order in which they join the tree.
+    assert_eq!(
+        vec![
+            (0, 1, 4),
+            (1, 2, 8),
+            (2, 8, 2),
+            (2, 5, 4),
+            (5, 6, 2),
+            (6, 7, 1),
+            (2, 3, 7),
+            (3, 4, 9)
+        ],
+        order.0
+    );
+}
+
+#[test]
+fn isolated_nodes() {
+    let mut g = SimpleGraph::<u32>::new();
+    for ii in 0..5 {
+        g.add_weighted_edges(ii, ii + 1, 1);
+    }
+    g.add_node(9);
+    g.add_node(9);
+    g.add_node(0);
+
+    assert_eq!(7, g.n_nodes());
+    assert_eq!(0, g.degree(9));
+
+    let lsp = g.sssp_dijkstra_lazy(0);
+    assert!(!lsp.get(9).is_feasible());
+    assert!(!lsp.get(7).is_feasible());
+    assert_eq!(5, lsp.get(5).dist());
+
+    let (edges, dist) = mst_prim_edges(&g, 0);
+    assert_eq!(5, edges.len());
+    assert_eq!(5, dist);
+}
+
+#[test]
+fn dag_shortest_path() {
+    let mut g = SimpleGraph::<i32>::new();
+    g.add_weighted_arc(0, 1, 5);
+    g.add_weighted_arc(0, 2, 3);
+    g.add_weighted_arc(1, 3, 6);
+    g.add_weighted_arc(1, 2, 2);
+    g.add_weighted_arc(2, 4, 4);
+    g.add_weighted_arc(2, 5, 2);
+    g.add_weighted_arc(2, 3, 7);
+    g.add_weighted_arc(3, 4, -1);
+    g.add_weighted_arc(4, 5, -2);
+
+    let order = g.topological_sort().unwrap();
+    assert_eq!(6, order.len());
+    for ii in 0..6 {
+        for (u, _) in g.neighbours(ii) {
+            let pos = |x| order.iter().position(|n| *n == x).unwrap();
+            assert!(pos(ii) < pos(u));
+        }
+    }
+
+    let lsp = g.dag_shortest_path(1).unwrap();
+    assert!(!lsp.get(0).is_feasible());
+    assert_eq!(2, lsp.get(2).dist());
+    assert_eq!(6, lsp.get(3).dist());
+    assert_eq!(5, lsp.get(4).dist());
+    assert_eq!(3, lsp.get(5).dist());
+    assert_eq!(&[1, 3, 4, 5], lsp.get(5).path());
+
+    // Bellman-Ford agrees on the distances despite the negative weights.
+    let bf = g.sssp_bellman_ford(1).unwrap();
+    for ii in 0..6 {
+        assert_eq!(lsp.dist(ii), bf.dist(ii));
+    }
+
+    g.add_weighted_arc(5, 1, 1);
+    g.add_weighted_arc(0, 6, 1);
+    g.add_weighted_arc(5, 7, 1);
+    let err: NotADag = g.topological_sort().unwrap_err();
+    assert!((1..6).contains(&err.node()));
+    assert!(g.dag_shortest_path(0).is_err());
+
+    // Node 2 is reachable from the cycle, but not on it.
+    let mut c = SimpleGraph::<i32>::new();
+    c.add_weighted_arc(0, 1, 1);
+    c.add_weighted_arc(1, 2, 1);
+    c.add_weighted_arc(1, 0, 1);
+    let node = c.topological_sort().unwrap_err().node();
+    assert!(node == 0 || node == 1);
+
+    let mut u = SimpleGraph::<i32>::new();
+    u.add_weighted_edges(0, 1, 1);
+    assert!(u.dag_shortest_path(0).is_err());
+}
+
+#[test]
+fn from_edges() {
+    let edges = vec![(0, 1, 7), (0, 2, 9), (1, 2, 10)];
+    let g = SimpleGraph::from_edges(edges.clone());
+
+    let mut h = SimpleGraph::<u32>::new();
+    for (n1, n2, w) in &edges {
+        h.add_weighted_edges(*n1, *n2, *w);
+    }
+
+    assert_eq!(h.n_nodes(), g.n_nodes());
+    assert_eq!(h.n_edges(), g.n_edges());
+    assert_eq!(Some(&10), g.edge_weight(2, 1));
+
+    let mut g: SimpleGraph<u32> = edges.into_iter().collect();
+    g.extend(vec![(2, 3, 1), (3, 4, 2)]);
+    assert_eq!(5, g.n_nodes());
+    assert_eq!(10, g.n_edges());
+    assert_eq!(Some(&2), g.edge_weight(4, 3));
+}
+
+#[test]
+fn add_weighted_edges_update() {
+    let mut g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7), (1, 2, 4), (1, 0, 3)]);
+    assert_eq!(4, g.n_edges());
+    assert_eq!(1, g.degree(0));
+    assert_eq!(Some(&3), g.edge_weight(0, 1));
+
+    g.finalize();
+    g.add_weighted_edges(2, 1, 5);
+    assert_eq!(4, g.n_edges());
+    assert_eq!(Some(&5), g.edge_weight(1, 2));
+
+    // Only the missing direction of a half existing edge is added.
+    g.add_weighted_arc(3, 4, 1);
+    g.add_weighted_edges(4, 3, 2);
+    assert_eq!(6, g.n_edges());
+    assert_eq!(Some(&2), g.edge_weight(3, 4));
+
+    // Self-loops are skipped and not counted.
+    g.add_weighted_edges(2, 2, 1);
+    g.add_weighted_edge_with(5, 5, 1, ());
+    assert_eq!(6, g.n_edges());
+    assert!(!g.has_edge(2, 2));
+
+    let mut streets = SimpleGraph::with_edge_data();
+    streets.add_weighted_edge_with(0, 0, 1, "Loop");
+    streets.add_weighted_edge_with(0, 1, 5, "Old Road");
+    streets.add_weighted_edge_with(1, 0, 2, "New Road");
+    assert_eq!(2, streets.n_edges());
+    assert_eq!(None, streets.edge_data(0, 0));
+    assert_eq!(Some(&"New Road"), streets.edge_data(0, 1));
+    assert_eq!(Some(&2), streets.edge_weight(0, 1));
+}
+
+#[test]
+fn dijkstra_with_buffer() {
+    let mut g = SimpleGraph::<u32>::with_capacity(6);
+
+    g.add_weighted_edges(0, 1, 7);
+    g.add_weighted_edges(0, 2, 9);
+    g.add_weighted_edges(0, 5, 14);
+    g.add_weighted_edges(1, 2, 10);
+    g.add_weighted_edges(1, 3, 15);
+    g.add_weighted_edges(2, 5, 2);
+    g.add_weighted_edges(2, 3, 11);
+    g.add_weighted_edges(3, 4, 6);
+    g.add_weighted_edges(4, 5, 9);
+
+    let mut buffer = DijkstraBuffer::with_capacity(6);
+
+    for src in 0..6 {
+        let expected = g.sssp_dijkstra(src, &[0, 1, 2, 3, 4, 5]);
+        let got = g.sssp_dijkstra_with_buffer(src, &[0, 1, 2, 3, 4, 5], &mut buffer);
+
+        for (e, r) in expected.iter().zip(got.iter()) {
+            assert_eq!(e.is_feasible(), r.is_feasible());
+            assert_eq!(e.dist(), r.dist());
+            assert_eq!(e.path(), r.path());
+        }
+    }
+}
+
+#[test]
+fn eq_and_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(ph: &PairingHeap<i32, i32>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        ph.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let (ph1, _) = create_heap(1, 11);
+    let mut ph2 = PairingHeap::new();
+    for ii in (1..11).rev() {
+        ph2.insert(ii, ii);
+    }
+
+    assert!(ph1 == ph2);
+    assert_eq!(hash(&ph1), hash(&ph2));
+
+    ph2.decrease_prio(&5, 1);
+    assert!(ph1 != ph2);
+
+    ph2.change_prio_by_key(&5, 5);
+    ph2.insert(11, 11);
+    assert!(ph1 != ph2);
+
+    assert!(PairingHeap::<i32, i32>::new() == PairingHeap::new());
+}
+
+#[test]
+fn by_key() {
+    #[derive(Debug, PartialEq)]
+    struct Job {
+        id: u32,
+        cost: f64,
+    }
+
+    fn cost(j: &Job) -> &f64 {
+        &j.cost
+    }
+
+    let mut ph = crate::PairingHeapByKey::new_by_key(cost);
+    assert!(ph.is_empty());
+
+    for (id, cost) in [(1, 2.5), (2, 0.5), (3, 1.5)].iter() {
+        ph.insert(Job {
+            id: *id,
+            cost: *cost,
+        });
+    }
+
+    let mut other = crate::PairingHeapByKey::new_by_key(cost);
+    other.insert(Job { id: 4, cost: 0.1 });
+
+    let mut ph = ph.merge(other);
+    assert_eq!(4, ph.len());
+    assert_eq!(4, ph.find_min().unwrap().id);
+
+    let ids: Vec<_> = std::iter::from_fn(|| ph.delete_min())
+        .map(|j| j.id)
+        .collect();
+    assert_eq!(vec![4, 2, 3, 1], ids);
+
+    // The extractor is stored once, so it needn't be Copy, e.g. when it owns its configuration.
+    let orders = [("deadline", vec![2, 3, 1]), ("id", vec![1, 2, 3])];
+    for (field, expected) in orders.iter() {
+        let field = field.to_string();
+        let mut ph =
+            crate::PairingHeapByKey::new_by_key(
+                move |t: &(u32, u32)| {
+                    if field == "deadline" {
+                        &t.1
+                    } else {
+                        &t.0
+                    }
+                },
+            );
+        for task in [(1, 30), (2, 10), (3, 20)].iter() {
+            ph.insert(*task);
+        }
+
+        let ids: Vec<_> = std::iter::from_fn(|| ph.delete_min())
+            .map(|t| t.0)
+            .collect();
+        assert_eq!(expected, &ids);
+    }
+}
+
+#[test]
+fn finalize() {
+    let mut g = SimpleGraph::<u32>::new();
+    g.reserve_nodes(10);
+    g.reserve_edges_for(3, 4);
+    assert_eq!(1, g.n_nodes());
+
+    g.add_weighted_edges(3, 5, 1);
+    g.add_weighted_edges(3, 1, 2);
+    g.add_weighted_edges(3, 4, 3);
+    g.add_weighted_arc(3, 1, 4);
+
+    g.finalize();
+    g.shrink_to_fit();
+
+    let nb: Vec<_> = g.neighbours(3).map(|(idx, _)| idx).collect();
+    assert_eq!(vec![1, 1, 4, 5], nb);
+    assert_eq!(Some(&2), g.edge_weight(3, 1));
+    assert_eq!(Some(&1), g.edge_weight(3, 5));
+    assert_eq!(None, g.edge_weight(3, 2));
+    assert_eq!(None, g.edge_weight(3, 6));
+    assert_eq!(None, g.edge_weight(7, 3));
+
+    g.add_weighted_edges(3, 0, 5);
+    assert_eq!(Some(&5), g.edge_weight(3, 0));
+}
+
+#[test]
+fn stable() {
+    let mut ph = StablePairingHeap::<i32, i32>::new_stable();
+    for ii in 0..20 {
+        ph.insert(ii, ii % 3);
+    }
+
+    ph.decrease_prio(&19, 1);
+    ph.change_prio_by_key(&0, 2);
+
+    let mut keys = Vec::new();
+    while let Some((k, _)) = ph.delete_min() {
+        keys.push(k);
+    }
+
+    let expected = vec![
+        3, 6, 9, 12, 15, 18, 19, 1, 4, 7, 10, 13, 16, 0, 2, 5, 8, 11, 14, 17,
+    ];
+    assert_eq!(expected, keys);
+}
+
+#[test]
+fn decrease_prio_many() {
+    let (mut ph, _) = create_heap(1, 11);
+
+    ph.delete_min();
+    ph.decrease_prio_many(&[(8, 4), (6, 3), (9, 3), (10, 2), (42, 1)]);
+    ph.decrease_prio_many(&[]);
+
+    let key_exp = vec![2, 6, 3, 8, 4, 5, 9, 7, 10];
+    let prio_exp = vec![2, 3, 3, 4, 4, 5, 6, 7, 8];
+
+    let mut count = 0;
+    while let Some((k, p)) = ph.delete_min() {
+        assert_eq!(key_exp[count], k);
+        assert_eq!(prio_exp[count], p);
+        count += 1;
+    }
+    assert_eq!(9, count);
+
+    let (mut ph, _) = create_heap(0, 100);
+    let updates: Vec<_> = (0..100)
+        .filter(|ii| ii % 2 == 1)
+        .map(|ii| (ii, 100))
+        .collect();
+    ph.decrease_prio_many(&updates);
+
+    let mut prev = i32::MIN;
+    while let Some((_, p)) = ph.delete_min() {
+        assert!(prev <= p);
+        prev = p;
+    }
+}
+
+#[test]
+fn merge_graphs() {
+    let mut west = SimpleGraph::<u32>::new();
+    west.add_weighted_edges(0, 1, 7);
+    west.add_weighted_edges(0, 2, 9);
+    west.add_weighted_edges(1, 2, 10);
+    west.add_weighted_edges(2, 3, 11);
+
+    let mut east = SimpleGraph::<u32>::new();
+    east.add_weighted_edges(2, 3, 4);
+    east.add_weighted_edges(3, 4, 6);
+    east.add_weighted_edges(4, 5, 9);
+    east.add_node(6);
+
+    let mut g = west.merge(east);
+    assert_eq!(7, g.n_nodes());
+    assert_eq!(12, g.n_edges());
+    assert_eq!(Some(&4), g.edge_weight(2, 3));
+    assert_eq!(Some(&4), g.edge_weight(3, 2));
+
+    let sp = g.sssp_dijkstra(0, &[5]).pop().unwrap();
+    assert_eq!(28, sp.dist());
+    assert_eq!(&[0, 2, 3, 4, 5], sp.path());
+
+    let mut other = SimpleGraph::<u32>::new();
+    other.add_weighted_edges(0, 1, 3);
+    g.merge_from_with(&other, |w1, w2| w1 + w2);
+    assert_eq!(Some(&10), g.edge_weight(1, 0));
+    assert_eq!(12, g.n_edges());
+}
+
+#[test]
+fn is_valid() {
+    assert!(PairingHeap::<i32, i32>::new().is_valid());
+
+    let (mut ph, v) = create_heap(0, 50);
+    assert!(ph.is_valid());
+
+    ph.delete_min();
+    ph.change_prio(&v[40], 0);
+    ph.decrease_prio(&30, 29);
+    ph.change_prio_by_key(&5, 100);
+    assert!(ph.is_valid());
+
+    let ph = ph.merge(create_heap(50, 100).0);
+    assert!(ph.is_valid());
+    assert_eq!(99, ph.len());
+}
+
+#[test]
+fn dedup_edges() {
+    // The edge between node 0 and 1 is stored three times, e.g. after reading a list of arcs.
+    let build = || {
+        let mut g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 4), (1, 2, 3), (0, 2, 2)]);
+        for w in [1, 9] {
+            g.add_weighted_arc(0, 1, w);
+            g.add_weighted_arc(1, 0, w);
+        }
+        g
+    };
+
+    let mut g = build();
+    assert_eq!(10, g.n_edges());
+    g.dedup_edges();
+    assert_eq!(6, g.n_edges());
+    assert_eq!(Some(&1), g.edge_weight(1, 0));
+    assert_eq!(3, mst_prim(&g, 0).1);
+
+    // Without deduplication, Prim's algorithm picks the stale weight 1 for the updated edge.
+    let mut g = build();
+    assert_eq!(3, mst_prim(&g, 0).1);
+    g.dedup_edges_with(|_, w| w);
+    assert_eq!(6, g.n_edges());
+    assert_eq!(Some(&9), g.edge_weight(0, 1));
+    assert_eq!(5, mst_prim(&g, 0).1);
+    assert_eq!(vec![(1, &9), (2, &2)], g.neighbours(0).collect::<Vec<_>>());
+}
+
+#[test]
+fn stats() {
+    let g = SimpleGraph::<u32>::new();
+    let stats = g.stats();
+    assert_eq!(0, stats.n_nodes());
+    assert_eq!(0, stats.min_degree());
+    assert!(stats.is_connected());
+
+    let mut g = SimpleGraph::from_edges(vec![(0, 1, 1), (0, 2, 1), (0, 3, 1), (1, 2, 1)]);
+    let stats = g.stats();
+    assert_eq!(4, stats.n_nodes());
+    assert_eq!(8, stats.n_edges());
+    assert_eq!(1, stats.min_degree());
+    assert_eq!(3, stats.max_degree());
+    assert_eq!(2.0, stats.mean_degree());
+    assert_eq!(8.0 / 12.0, stats.density());
+    assert!(stats.is_connected());
+
+    g.add_weighted_edges(5, 6, 1);
+    let stats = g.stats();
+    assert!(!stats.is_connected());
+    assert!(!g.is_connected());
+    assert!(format!("{:?}", stats).contains("GraphStats"));
+}
+
+#[test]
+fn dijkstra_for_each() {
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+    ]);
+
+    let lsp = g.sssp_dijkstra_lazy(0);
+    let mut count = 0;
+
+    g.sssp_dijkstra_for_each(0, |sp| {
+        let expected = lsp.get(sp.dest());
+        assert_eq!(0, sp.src());
+        assert_eq!(expected.dist(), sp.dist());
+        assert_eq!(expected.path(), sp.path());
+        count += 1;
+    });
+
+    assert_eq!(6, count);
+}
+
+#[test]
+fn dijkstra_within() {
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+    ]);
+
+    let lsp = g.sssp_dijkstra_within(0, 11);
+    assert_eq!(vec![0, 1, 2, 5], lsp.settled_nodes().collect::<Vec<_>>());
+    assert_eq!(11, lsp.get(5).dist());
+    assert_eq!(&[0, 2, 5], lsp.get(5).path());
+    assert!(!lsp.get(3).is_feasible());
+    assert!(!lsp.get(4).is_feasible());
+
+    let lsp = g.sssp_dijkstra_within(0, 10);
+    assert_eq!(vec![0, 1, 2], lsp.settled_nodes().collect::<Vec<_>>());
+
+    let lsp = g.sssp_dijkstra_within(0, 100);
+    assert_eq!(6, lsp.settled_nodes().count());
+    assert_eq!(20, lsp.get(4).dist());
+}
+
+#[test]
+fn value_heap() {
+    let mut ph = crate::ValueHeap::<i32>::new();
+    for ii in [5, 3, 8, 1].iter() {
+        ph.insert_value(*ii);
+    }
+
+    assert_eq!(Some(&1), ph.find_min_value());
+    assert_eq!(Some(1), ph.delete_min_value());
+    assert_eq!(Some(3), ph.delete_min_value());
+    assert_eq!(2, ph.len());
+}
+
+#[test]
+fn dijkstra_visit() {
+    #[derive(Default)]
+    struct Recorder {
+        settled: Vec<(usize, u32)>,
+        relaxed: Vec<(usize, usize, u32)>,
+        skipped: Vec<usize>,
+    }
+
+    impl DijkstraVisitor<u32> for Recorder {
+        fn on_settle(&mut self, node: usize, dist: u32) {
+            self.settled.push((node, dist));
+        }
+
+        fn on_relax(&mut self, from: usize, to: usize, new_dist: u32) {
+            self.relaxed.push((from, to, new_dist));
+        }
+
+        fn on_skip(&mut self, node: usize) {
+            self.skipped.push(node);
+        }
+    }
+
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+    ]);
+
+    let mut rec = Recorder::default();
+    let lsp = g.sssp_dijkstra_visit_with_strategy(0, DijkstraStrategy::DecreaseKey, &mut rec);
+
+    assert_eq!(
+        vec![(0, 0), (1, 7), (2, 9), (5, 11), (3, 20), (4, 20)],
+        rec.settled
+    );
+    assert!(rec.relaxed.contains(&(2, 5, 11)));
+    assert!(rec.relaxed.contains(&(2, 3, 20)));
+    // Decrease-key never leaves outdated entries in the priority queue.
+    assert!(rec.skipped.is_empty());
+    assert_eq!(20, lsp.get(4).dist());
+
+    // Reinserting leaves the entries of the first paths to nodes 5 and 3 behind.
+    let mut rec = Recorder::default();
+    let ri = g.sssp_dijkstra_visit_with_strategy(0, DijkstraStrategy::Reinsert, &mut rec);
+    assert_eq!(vec![5, 3], rec.skipped);
+    assert_eq!(lsp.predecessors(), ri.predecessors());
+}
+
+#[test]
+fn dijkstra_strategies() {
+    // A grid with unit weights has many shortest paths of the same length.
+    let n = 8;
+    let mut g = SimpleGraph::<u32>::new();
+    for ii in 0..n {
+        for jj in 0..n {
+            if ii + 1 < n {
+                g.add_weighted_edges(ii * n + jj, (ii + 1) * n + jj, 1);
+            }
+            if jj + 1 < n {
+                g.add_weighted_edges(ii * n + jj, ii * n + jj + 1, 1);
+            }
+        }
+    }
+
+    let dk = g.sssp_dijkstra_lazy_with_strategy(0, DijkstraStrategy::DecreaseKey);
+    let ri = g.sssp_dijkstra_lazy_with_strategy(0, DijkstraStrategy::Reinsert);
+
+    assert_eq!(dk.predecessors(), ri.predecessors());
+    for node in 0..n * n {
+        assert_eq!(dk.get(node).dist(), ri.get(node).dist());
+    }
+    assert_eq!(14, dk.get(n * n - 1).dist());
+}
+
+#[test]
+fn dijkstra_strategies_ties() {
+    // Node 2 has two shortest paths of distance 6, via node 4 and via node 6. The order of the edges
+    // decides the order of the heap operations, which must not influence the tie below.
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (3, 6, 2),
+        (1, 0, 2),
+        (4, 6, 2),
+        (3, 5, 2),
+        (2, 6, 1),
+        (3, 4, 2),
+        (0, 5, 2),
+        (2, 4, 1),
+        (3, 1, 1),
+    ]);
+
+    // The tied nodes 4 and 6 leave the queue by ascending index with both strategies, so that node 4
+    // relaxes node 2 first.
+    let dk = g.sssp_dijkstra_lazy_with_strategy(0, DijkstraStrategy::DecreaseKey);
+    let ri = g.sssp_dijkstra_lazy_with_strategy(0, DijkstraStrategy::Reinsert);
+    let default = g.sssp_dijkstra_lazy(0);
+
+    let expected = vec![None, Some(0), Some(4), Some(1), Some(3), Some(0), Some(3)];
+    assert_eq!(expected, dk.predecessors());
+    assert_eq!(expected, ri.predecessors());
+    assert_eq!(expected, default.predecessors());
+
+    assert_eq!(vec![0, 1, 3, 4, 2], dk.get(2).path());
+    assert_eq!(dk.get(2).path(), ri.get(2).path());
+}
+
+#[test]
+fn tree_shape() {
+    let ph = PairingHeap::<i32, i32>::new();
+    assert_eq!(0, ph.max_depth());
+    assert_eq!(0, ph.max_children());
+
+    // Inserting in ascending order attaches every node directly to the root.
+    let (mut ph, _) = create_heap(0, 10);
+    assert_eq!(2, ph.max_depth());
+    assert_eq!(9, ph.max_children());
+
+    // Inserting in descending order makes every new node the root of a chain.
+    let mut chain = PairingHeap::<i32, i32>::new();
+    for ii in (0..10).rev() {
+        chain.insert(ii, ii);
+    }
+    assert_eq!(10, chain.max_depth());
+    assert_eq!(1, chain.max_children());
+
+    ph.delete_min();
+    assert!(ph.max_children() < 9);
+}
+
+#[test]
+fn dijkstra_update_edge() {
+    let edges = vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+        (6, 7, 1),
+    ];
+
+    // Decrease and increase weights of edges inside and outside the shortest path tree.
+    for &(u, v, new_w) in &[
+        (2, 5, 20),
+        (0, 5, 1),
+        (2, 3, 1),
+        (1, 3, 30),
+        (0, 2, 3),
+        (6, 7, 5),
+    ] {
+        let mut g = SimpleGraph::<u32>::from_edges(edges.clone());
+        let mut lsp = g.sssp_dijkstra_lazy(0);
+        g.update_edge_lazy(&mut lsp, u, v, new_w);
+
+        // The graph is changed together with the result.
+        assert_eq!(Some(&new_w), g.edge_weight(u, v));
+        assert_eq!(Some(&new_w), g.edge_weight(v, u));
+
+        let changed = SimpleGraph::<u32>::from_edges(edges.iter().map(|&(a, b, w)| {
+            if (a, b) == (u, v) {
+                (a, b, new_w)
+            } else {
+                (a, b, w)
+            }
+        }));
+        let expected = changed.sssp_dijkstra_lazy(0);
+
+        for node in 0..8 {
+            let (sp, ep) = (lsp.get(node), expected.get(node));
+            assert_eq!(ep.is_feasible(), sp.is_feasible());
+            assert_eq!(ep.dist(), sp.dist());
+            assert_eq!(
+                ep.dist(),
+                sp.path()
+                    .windows(2)
+                    .map(|p| *changed.edge_weight(p[0], p[1]).unwrap())
+                    .sum::<u32>()
+            );
+        }
+    }
+
+    // Missing edges are left alone, and arcs are only changed in their direction.
+    let mut g = SimpleGraph::<u32>::from_edges(edges);
+    let mut lsp = g.sssp_dijkstra_lazy(0);
+    assert!(!g.set_edge_weight(0, 4, 1));
+    g.update_edge_lazy(&mut lsp, 0, 4, 1);
+    assert!(!g.has_edge(0, 4));
+    assert_eq!(20, lsp.get(4).dist());
+
+    g.add_weighted_arc(0, 4, 30);
+    assert!(g.set_edge_weight(0, 4, 2));
+    assert_eq!(Some(&2), g.edge_weight(0, 4));
+    assert_eq!(None, g.edge_weight(4, 0));
+}
+
+#[test]
+fn dijkstra_traced() {
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+        (6, 7, 1),
+    ]);
+
+    let (lsp, order) = g.sssp_dijkstra_traced(0);
+    assert_eq!(vec![0, 1, 2, 5, 3, 4], order);
+    assert_eq!(11, lsp.get(5).dist());
+    assert!(!lsp.get(6).is_feasible());
+}
+
+#[test]
+fn dijkstra_zero_weights() {
+    #[derive(Default)]
+    struct Counter {
+        relaxed: usize,
+        skipped: usize,
+    }
+
+    impl DijkstraVisitor<u32> for Counter {
+        fn on_relax(&mut self, _from: usize, _to: usize, _new_dist: u32) {
+            self.relaxed += 1;
+        }
+
+        fn on_skip(&mut self, _node: usize) {
+            self.skipped += 1;
+        }
+    }
+
+    // A chain of zero-weight edges, in which every node is also linked to all nodes after it.
+    let n = 50;
+    let mut g = SimpleGraph::<u32>::new();
+    for ii in 0..n {
+        for jj in ii + 1..n {
+            g.add_weighted_edges(ii, jj, if jj == ii + 1 { 0 } else { 1 });
+        }
+    }
+    g.add_weighted_edges(n - 1, n, 3);
+
+    let mut counter = Counter::default();
+    let lsp = g.sssp_dijkstra_visit_with_strategy(0, DijkstraStrategy::DecreaseKey, &mut counter);
+
+    // Paths of equal distance are never relaxed: node 0 relaxes all other nodes, and each node on the chain
+    // only its successor. No duplicate entries are popped, so the heap holds at most one entry per node.
+    assert_eq!(2 * (n - 1), counter.relaxed);
+    assert_eq!(0, counter.skipped);
+
+    for strategy in [DijkstraStrategy::DecreaseKey, DijkstraStrategy::Reinsert] {
+        let other = g.sssp_dijkstra_lazy_with_strategy(0, strategy);
+        for ii in 1..n {
+            assert_eq!(0, other.get(ii).dist());
+            assert_eq!(ii, other.get(ii).path().len() - 1);
+        }
+        assert_eq!(3, other.get(n).dist());
+        assert_eq!(lsp.get(n).path(), other.get(n).path());
+    }
+}
+
+#[test]
+fn dijkstra_visit_stop() {
+    struct StopAt(usize);
+
+    impl DijkstraVisitor<u32> for StopAt {
+        fn on_pop(&mut self, node: usize, _dist: u32) -> bool {
+            node != self.0
+        }
+    }
+
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+    ]);
+
+    let lsp = g.sssp_dijkstra_visit(0, &mut StopAt(2));
+    assert_eq!(vec![0, 1, 2], lsp.settled_nodes().collect::<Vec<_>>());
+    assert_eq!(9, lsp.get(2).dist());
+    assert!(!lsp.get(5).is_feasible());
+    assert!(!lsp.get(3).is_feasible());
+}
+
+#[test]
+fn k_shortest_paths() {
+    // The example graph from the Wikipedia article on Yen's algorithm, with C = 0, D = 1, ..., H = 5.
+    let mut g = SimpleGraph::<u32>::new();
+    for &(from, to, w) in &[
+        (0, 1, 3),
+        (0, 2, 2),
+        (1, 3, 4),
+        (2, 1, 1),
+        (2, 3, 2),
+        (2, 4, 3),
+        (3, 4, 2),
+        (3, 5, 1),
+        (4, 5, 2),
+    ] {
+        g.add_weighted_arc(from, to, w);
+    }
+
+    let ksp = g.k_shortest_paths(0, 5, 3);
+    assert_eq!(3, ksp.len());
+    assert_eq!((5, &[0, 2, 3, 5][..]), (ksp[0].dist(), ksp[0].path()));
+    assert_eq!((7, &[0, 2, 4, 5][..]), (ksp[1].dist(), ksp[1].path()));
+    assert_eq!((8, &[0, 1, 3, 5][..]), (ksp[2].dist(), ksp[2].path()));
+
+    // There are only seven loopless paths from C to H.
+    let all = g.k_shortest_paths(0, 5, 100);
+    assert_eq!(7, all.len());
+    assert!(all.windows(2).all(|w| w[0].dist() <= w[1].dist()));
+    for sp in &all {
+        let mut nodes = sp.path().to_vec();
+        nodes.sort_unstable();
+        nodes.dedup();
+        assert_eq!(sp.path().len(), nodes.len());
+    }
+
+    assert!(g.k_shortest_paths(5, 0, 3).is_empty());
+    assert!(g.k_shortest_paths(0, 5, 0).is_empty());
+}
+
+#[test]
+fn lazy_try_get() {
+    let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7), (1, 2, 3)]);
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    assert_eq!(10, lsp.try_get(2).unwrap().dist());
+    assert!(lsp.try_get(3).is_none());
+    assert!(lsp.try_get(usize::MAX).is_none());
+}
+
+#[test]
+fn bellman_ford() {
+    // The example from "Introduction to Algorithms", with s = 0, t = 1, x = 2, y = 3 and z = 4.
+    let mut g = SimpleGraph::<i32>::new();
+    for &(from, to, w) in &[
+        (0, 1, 6),
+        (0, 3, 7),
+        (1, 2, 5),
+        (1, 3, 8),
+        (1, 4, -4),
+        (2, 1, -2),
+        (3, 2, -3),
+        (3, 4, 9),
+        (4, 0, 2),
+        (4, 2, 7),
+    ] {
+        g.add_weighted_arc(from, to, w);
+    }
+
+    let lsp = g.sssp_bellman_ford(0).unwrap();
+    assert_eq!(
+        (2, &[0, 3, 2, 1][..]),
+        (lsp.get(1).dist(), lsp.get(1).path())
+    );
+    assert_eq!((4, &[0, 3, 2][..]), (lsp.get(2).dist(), lsp.get(2).path()));
+    assert_eq!((7, &[0, 3][..]), (lsp.get(3).dist(), lsp.get(3).path()));
+    assert_eq!(
+        (-2, &[0, 3, 2, 1, 4][..]),
+        (lsp.get(4).dist(), lsp.get(4).path())
+    );
+
+    let mut g = SimpleGraph::<i32>::new();
+    for &(from, to, w) in &[(0, 1, 1), (1, 2, -1), (2, 3, -1), (3, 1, 1), (3, 4, 2)] {
+        g.add_weighted_arc(from, to, w);
+    }
+
+    let err: NegativeCycle = g.sssp_bellman_ford(0).unwrap_err();
+    let mut cycle = err.cycle().to_vec();
+    let pos = cycle.iter().position(|&n| n == 1).unwrap();
+    cycle.rotate_left(pos);
+    assert_eq!(vec![1, 2, 3], cycle);
+
+    // The cycle is not reachable from node 4.
+    assert!(g.sssp_bellman_ford(4).is_ok());
+}
+
+#[test]
+fn from_edges_dedup() {
+    let edges = vec![(0, 1, 7), (1, 2, 5), (2, 1, 3), (0, 1, 9)];
+
+    let g = SimpleGraph::<u32>::from_edges(edges.clone());
+    assert_eq!(4, g.n_edges());
+    assert_eq!(Some(&9), g.edge_weight(1, 0));
+
+    let g = SimpleGraph::<u32>::from_edges_dedup(edges);
+    assert_eq!(4, g.n_edges());
+    assert_eq!(Some(&7), g.edge_weight(1, 0));
+    assert_eq!(Some(&3), g.edge_weight(1, 2));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn dijkstra_par() {
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+        (6, 7, 1),
+    ]);
+
+    let sources = [4, 0, 7, 2];
+    let par = g.sssp_dijkstra_many_par(&sources);
+    assert_eq!(sources.len(), par.len());
+
+    for (src, lsp) in sources.iter().zip(&par) {
+        let serial = g.sssp_dijkstra_lazy(*src);
+        assert_eq!(serial.predecessors(), lsp.predecessors());
+        for node in 0..8 {
+            assert_eq!(serial.get(node).dist(), lsp.get(node).dist());
+        }
+    }
+
+    assert_eq!(8, g.apsp_dijkstra_par().len());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn lazy_get_all_par() {
+    let mut g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7), (0, 2, 9), (1, 2, 10), (2, 3, 11)]);
+    g.add_weighted_edges(5, 6, 2);
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    let serial = lsp.get_all();
+    let parallel = lsp.get_all_par();
+    assert_eq!(serial.len(), parallel.len());
+
+    for (sp1, sp2) in serial.iter().zip(&parallel) {
+        assert_eq!(sp1.dest(), sp2.dest());
+        assert_eq!(sp1.is_feasible(), sp2.is_feasible());
+        assert_eq!(sp1.dist(), sp2.dist());
+        assert_eq!(sp1.path(), sp2.path());
+    }
+}
+
+#[test]
+fn max_heap_merge() {
+    let mut ph1 = MaxPairingHeap::<i32, i32>::new();
+    let mut ph2 = MaxPairingHeap::<i32, i32>::new();
+    for ii in 0..10 {
+        ph1.insert(ii, ii);
+        ph2.insert(ii + 10, 2 * ii + 1);
+    }
+
+    // The maximum of the second heap is larger, its root must become the new root.
+    let mut ph = ph1.merge(ph2);
+    assert_eq!(20, ph.len());
+    assert_eq!(Some((&19, &19)), ph.find_max());
+
+    let mut prev = i32::MAX;
+    while let Some((_, prio)) = ph.delete_max() {
+        assert!(prio <= prev);
+        prev = prio;
+    }
+    assert!(ph.is_empty());
+}
+
+#[test]
+fn binary_heap_conversion() {
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    let values: Vec<i32> = (0..200).map(|ii| (ii * 37) % 101).collect();
+    let mut sorted = values.clone();
+    sorted.sort_unstable();
+
+    // A min-heap of the standard library and a pairing heap return the same order.
+    let bh: BinaryHeap<_> = values.iter().map(|v| Reverse(*v)).collect();
+    let mut ph = PairingHeap::<i32, i32>::from(bh);
+    assert_eq!(200, ph.len());
+    assert!(ph.is_valid());
+    assert_eq!(Some((&0, &0)), ph.find_min());
+    ph.delete_min();
+
+    let mut bh = BinaryHeap::<Reverse<i32>>::from(ph);
+    assert_eq!(199, bh.len());
+    for value in &sorted[1..] {
+        assert_eq!(Some(Reverse(*value)), bh.pop());
+    }
+
+    // A heap of the standard library is a max-heap, so the values come out of a pairing heap in reverse order.
+    let bh: BinaryHeap<_> = values.iter().copied().collect();
+    let mut ph = PairingHeap::<i32, i32>::from(bh);
+    assert_eq!(200, ph.len());
+    assert!(ph.is_valid());
+    assert_eq!(Some((0, 0)), ph.delete_min());
+    assert_eq!(Some((&sorted[1], &sorted[1])), ph.find_min());
+
+    let mut bh = BinaryHeap::<i32>::from(ph);
+    assert_eq!(199, bh.len());
+    for value in sorted[1..].iter().rev() {
+        assert_eq!(Some(*value), bh.pop());
+    }
+
+    // A max-pairing heap keeps the order of a heap of the standard library.
+    let mut ph = MaxPairingHeap::from(values.iter().copied().collect::<BinaryHeap<_>>());
+    assert_eq!(Some((&100, &100)), ph.find_max());
+    ph.delete_max();
+    ph.insert(500, 500);
+
+    let bh = BinaryHeap::from(ph);
+    assert_eq!(200, bh.len());
+    assert_eq!(Some(&500), bh.peek());
+    let mut expected = sorted[..199].to_vec();
+    expected.push(500);
+    assert_eq!(expected, bh.into_sorted_vec());
+}
+
+#[test]
+fn alt_query() {
+    let side = 12;
+    let mut g = SimpleGraph::<u32>::new();
+    for row in 0..side {
+        for col in 0..side {
+            let node = row * side + col;
+            let weight = ((row * 31 + col * 17) % 13 + 1) as u32;
+            if col + 1 < side {
+                g.add_weighted_edges(node, node + 1, weight);
+            }
+            if row + 1 < side {
+                g.add_weighted_arc(node, node + side, weight);
+            }
+        }
+    }
+    // A separate component.
+    g.add_weighted_edges(200, 201, 5);
+
+    let alt = AltPreprocessed::build(&g, 4);
+    assert_eq!(4, alt.landmarks().len());
+    assert!(alt.landmarks().contains(&200) || alt.landmarks().contains(&201));
+
+    for &(src, dest) in &[(0, 143), (143, 0), (5, 130), (77, 13), (0, 200), (200, 201)] {
+        let expected = &g.sssp_dijkstra(src, &[dest])[0];
+        let sp = alt.query(&g, src, dest);
+
+        assert_eq!(expected.is_feasible(), sp.is_feasible());
+        assert_eq!(expected.dist(), sp.dist());
+        let dist: u32 = sp
+            .path()
+            .windows(2)
+            .map(|p| *g.edge_weight(p[0], p[1]).unwrap())
+            .sum();
+        assert_eq!(expected.dist(), dist);
+    }
+}
+
+#[test]
+fn alt_query_all_pairs() {
+    use crate::graph::generate::erdos_renyi;
+
+    let n = 60;
+    let mut g = erdos_renyi(n, 3.0 / n as f64, 1..20u32, 7);
+    // Directed arcs make the distances to and from the landmarks differ.
+    for node in 0..n {
+        g.add_weighted_arc(node, (node * 7 + 3) % n, 1 + (node % 5) as u32);
+    }
+
+    let alt = AltPreprocessed::build(&g, 3);
+    for src in 0..n {
+        let lsp = g.sssp_dijkstra_lazy(src);
+        for dest in 0..n {
+            let (expected, sp) = (lsp.get(dest), alt.query(&g, src, dest));
+            assert_eq!(expected.is_feasible(), sp.is_feasible());
+            assert_eq!(expected.dist(), sp.dist());
+        }
+    }
+}
+
+#[test]
+fn payload() {
+    let mut ph = PairingHeap::<usize, u32, Vec<usize>>::new();
+    ph.insert_with(0, 5, vec![0]);
+    ph.insert_with(1, 2, vec![1]);
+    ph.insert_with(2, 8, vec![2]);
+    ph.change_prio_by_key(&2, 1);
+
+    assert_eq!(Some((&2, &1, &vec![2])), ph.find_min_with());
+    ph.find_min_data_mut().unwrap().push(7);
+
+    assert_eq!(Some((2, 1, vec![2, 7])), ph.delete_min_with());
+    assert_eq!(Some((1, 2, vec![1])), ph.delete_min_with());
+    assert_eq!(1, ph.len());
+}
+
+#[test]
+fn path_segments() {
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+        (6, 7, 1),
+    ]);
+
+    let lsp = g.sssp_dijkstra_lazy(0);
+    let sp = lsp.get(3);
+    assert_eq!(2, sp.hops());
+    assert_eq!(vec![(0, 2, 9), (2, 3, 11)], sp.segments());
+
+    let sp = lsp.get(7);
+    assert_eq!(0, sp.hops());
+    assert!(sp.segments().is_empty());
+
+    // Every path returned by get_all ends at its destination node.
+    for (ii, sp) in lsp
+        .get_all()
+        .iter()
+        .enumerate()
+        .filter(|(_, sp)| sp.is_feasible())
+    {
+        assert_eq!(Some(&ii), sp.path().last());
+        assert_eq!(sp.dist(), sp.segments().iter().map(|s| s.2).sum::<u32>());
+    }
+
+    for sp in g.k_shortest_paths(0, 4, 5) {
+        for (from, to, w) in sp.segments() {
+            assert_eq!(Some(&w), g.edge_weight(from, to));
+        }
+        assert_eq!(sp.dist(), sp.segments().iter().map(|s| s.2).sum::<u32>());
+    }
+}
+
+#[test]
+fn path_iter() {
+    let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7), (0, 2, 9), (1, 2, 10), (2, 3, 11)]);
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    let sp = lsp.get(3);
+    assert_eq!(3, sp.len());
+    assert!(!sp.is_empty());
+    assert_eq!(2, sp[1]);
+    assert_eq!(
+        vec![0, 2, 3],
+        (&sp).into_iter().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(vec![0, 2, 3], sp.into_iter().collect::<Vec<_>>());
+    assert_eq!(vec![0, 2, 3], lsp.get(3).into_path());
+
+    let mut g = g;
+    g.add_node(4);
+    let sp = g.sssp_dijkstra_lazy(0).get(4);
+    assert!(sp.is_empty());
+    assert_eq!(0, sp.into_iter().count());
+}
+
+#[test]
+fn from_iter_by() {
+    let items: Vec<i32> = (0..100).map(|ii| (ii * 37) % 100).collect();
+    let mut ph = PairingHeap::from_iter_by(items, |ii| -ii);
+
+    assert_eq!(100, ph.len());
+    assert!(ph.is_valid());
+
+    for ii in (0..100).rev() {
+        assert_eq!(Some((ii, -ii)), ph.delete_min());
+    }
+    assert!(ph.is_empty());
+
+    let ph = PairingHeap::<i32, i32>::from_iter_by(Vec::new(), |ii| *ii);
+    assert!(ph.is_empty());
+    assert!(ph.is_valid());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_shortest_paths() {
+    use crate::graph::{LazyShortestPaths, ShortestPath};
+
+    let g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (1, 2, 10),
+        (2, 3, 11),
+        (4, 5, 1),
+    ]);
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    let bytes = bincode::serialize(&lsp).unwrap();
+    let restored: LazyShortestPaths<u32> = bincode::deserialize(&bytes).unwrap();
+
+    for node in 0..6 {
+        let (expected, sp) = (lsp.get(node), restored.get(node));
+        assert_eq!(expected.is_feasible(), sp.is_feasible());
+        assert_eq!(expected.dist(), sp.dist());
+        assert_eq!(expected.path(), sp.path());
+    }
+
+    let sp = lsp.get(3);
+    let bytes = bincode::serialize(&sp).unwrap();
+    let restored: ShortestPath<u32> = bincode::deserialize(&bytes).unwrap();
+
+    assert_eq!(
+        (0, 3, 20),
+        (restored.src(), restored.dest(), restored.dist())
+    );
+    assert_eq!(&[0, 2, 3], restored.path());
+    assert_eq!(sp.segments(), restored.segments());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_alt() {
+    let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7), (0, 2, 9), (1, 2, 10), (2, 3, 11)]);
+    let alt = AltPreprocessed::build(&g, 2);
+
+    let bytes = bincode::serialize(&alt).unwrap();
+    let restored: AltPreprocessed<u32> = bincode::deserialize(&bytes).unwrap();
+
+    assert_eq!(alt, restored);
+    assert_eq!(&[0, 2, 3], restored.query(&g, 0, 3).path());
+}
+
+#[test]
+fn delete_multipass() {
+    let mut two_pass = PairingHeap::<i32, i32>::new();
+    let mut multipass = PairingHeap::<i32, i32>::with_delete_strategy(DeleteStrategy::Multipass);
+    assert_eq!(DeleteStrategy::TwoPass, two_pass.delete_strategy());
+    assert_eq!(DeleteStrategy::Multipass, multipass.delete_strategy());
+
+    for ii in 0..200 {
+        let prio = (ii * 73) % 200;
+        two_pass.insert(ii, prio);
+        multipass.insert(ii, prio);
+
+        if ii % 7 == 6 {
+            two_pass.decrease_prio(&(ii - 3), 50);
+            multipass.decrease_prio(&(ii - 3), 50);
+            assert_eq!(
+                two_pass.delete_min().map(|(_, prio)| prio),
+                multipass.delete_min().map(|(_, prio)| prio)
+            );
+        }
+    }
+
+    // Decreased priorities collide, so only the order of the priorities is deterministic.
+    assert!(multipass.is_valid());
+    while let Some((_, prio)) = two_pass.delete_min() {
+        assert_eq!(Some(prio), multipass.delete_min().map(|(_, prio)| prio));
+        assert!(multipass.is_valid());
+    }
+    assert!(multipass.is_empty());
+}
+
+#[test]
+fn lazy_dist_pred() {
+    let mut g = SimpleGraph::<u32>::with_capacity(6);
+
+    g.add_weighted_edges(0, 1, 7);
+    g.add_weighted_edges(0, 2, 9);
+    g.add_weighted_edges(0, 5, 14);
+    g.add_weighted_edges(1, 2, 10);
+    g.add_weighted_edges(1, 3, 15);
+    g.add_weighted_edges(2, 5, 2);
+    g.add_weighted_edges(2, 3, 11);
+    g.add_weighted_edges(3, 4, 6);
+    g.add_weighted_edges(4, 5, 9);
+
+    g.add_weighted_edges(6, 7, 2);
+    g.add_weighted_edges(6, 8, 3);
+
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    assert_eq!(Some(0), lsp.dist(0));
+    assert_eq!(None, lsp.pred(0));
+    assert!(lsp.is_reachable(0));
+
+    assert_eq!(Some(20), lsp.dist(4));
+    assert_eq!(Some(5), lsp.pred(4));
+    assert_eq!(Some(2), lsp.pred(5));
+    assert!(lsp.is_reachable(4));
+
+    for node in &[7, 8, 9, usize::MAX] {
+        assert_eq!(None, lsp.dist(*node));
+        assert_eq!(None, lsp.pred(*node));
+        assert!(!lsp.is_reachable(*node));
+    }
+
+    assert_eq!(
+        vec![
+            Some(0),
+            Some(7),
+            Some(9),
+            Some(20),
+            Some(20),
+            Some(11),
+            None,
+            None,
+            None
+        ],
+        lsp.distances()
+    );
+}
+
+#[test]
+fn delete_all_min() {
+    let mut ph = PairingHeap::<i32, i32>::new();
+    assert!(ph.delete_all_min().is_empty());
+
+    for ii in 0..30 {
+        ph.insert(ii, ii % 3);
+    }
+
+    for prio in 0..3 {
+        let mut batch = ph.delete_all_min();
+        assert_eq!(10, batch.len());
+        assert!(batch.iter().all(|(_, p)| *p == prio));
+
+        batch.sort_unstable();
+        let keys: Vec<i32> = batch.into_iter().map(|(key, _)| key).collect();
+        assert_eq!(
+            (0..30).filter(|ii| ii % 3 == prio).collect::<Vec<_>>(),
+            keys
+        );
+        assert!(ph.is_valid());
+    }
+
+    assert!(ph.is_empty());
+}
+
+#[test]
+fn total_weight() {
+    let mut g = SimpleGraph::<u32>::new();
+    assert_eq!(0, g.total_weight());
+
+    g.add_weighted_edges(0, 1, 7);
+    g.add_weighted_edges(2, 1, 3);
+    g.add_weighted_arc(1, 2, 5);
+    g.add_weighted_arc(2, 1, 5);
+    assert_eq!(15, g.total_weight());
+
+    g.add_weighted_arc(3, 0, 4);
+    g.add_weighted_arc(2, 3, 1);
+    assert_eq!(20, g.total_weight());
+
+    g.finalize();
+    assert_eq!(20, g.total_weight());
+}
+
+#[test]
+fn lazy_sparse_ids() {
+    let g = SimpleGraph::<u32>::from_edges(vec![(0, 10, 4), (10, 1000, 6), (2000, 3000, 1)]);
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    let sp = lsp.get(1000);
+    assert!(sp.is_feasible());
+    assert_eq!(10, sp.dist());
+    assert_eq!(&[0, 10, 1000], sp.path());
+
+    assert!(!lsp.get(3000).is_feasible());
+    assert!(!lsp.get(5000).is_feasible());
+    assert!(lsp.try_get(5000).is_none());
+    assert_eq!(None, lsp.dist(5000));
+
+    let sps = lsp.get_list(&[1000, 5000]);
+    assert!(sps[0].is_feasible());
+    assert!(!sps[1].is_feasible());
+
+    let sps = g.sssp_dijkstra(0, &[5000]);
+    assert!(!sps[0].is_feasible());
+}
+
+#[test]
+fn from_sorted() {
+    let mut ph = PairingHeap::from_sorted((0..100).map(|ii| (ii, ii / 2)).collect());
+    assert_eq!(100, ph.len());
+    assert!(ph.is_valid());
+
+    for ii in 0..50 {
+        assert_eq!(Some(ii / 2), ph.delete_min().map(|(_, prio)| prio));
+        assert!(ph.is_valid());
+    }
+
+    ph.insert(-1, -1);
+    assert_eq!(Some((-1, -1)), ph.delete_min());
+    assert_eq!(Some(25), ph.find_min().map(|(_, prio)| *prio));
+
+    // A long chain must not overflow the stack when the heap is dropped.
+    let ph = PairingHeap::from_sorted((0..1_000_000).map(|ii| (ii, ii)).collect());
+    assert_eq!(1_000_000, ph.max_depth());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn from_sorted_unsorted() {
+    PairingHeap::from_sorted(vec![(0, 2), (1, 1)]);
+}
+
+#[test]
+fn peek_n() {
+    let mut ph = PairingHeap::<i32, i32>::new();
+    assert!(ph.peek_n(3).is_empty());
+
+    for ii in 0..100 {
+        ph.insert(ii, (ii * 37) % 100);
+    }
+    for _ in 0..10 {
+        ph.delete_min();
+    }
+
+    let peeked: Vec<(i32, i32)> = ph.peek_n(10).into_iter().map(|(k, p)| (*k, *p)).collect();
+    assert_eq!(90, ph.len());
+    assert_eq!(90, ph.peek_n(200).len());
+    assert!(ph.peek_n(0).is_empty());
+
+    for elmt in peeked {
+        assert_eq!(Some(elmt), ph.delete_min());
+    }
+}
+
+#[test]
+fn dijkstra_any() {
+    let mut g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+    ]);
+    g.add_weighted_edges(6, 7, 2);
+
+    let sp = g.sssp_dijkstra_any(0, &[4, 5, 3]);
+    assert_eq!(5, sp.dest());
+    assert_eq!(11, sp.dist());
+    assert_eq!(&[0, 2, 5], sp.path());
+
+    let sp = g.sssp_dijkstra_any(4, &[0, 7, 100]);
+    assert_eq!((0, 20), (sp.dest(), sp.dist()));
+
+    let sp = g.sssp_dijkstra_any(0, &[3, 0]);
+    assert!(sp.is_feasible());
+    assert_eq!(0, sp.dist());
+    assert_eq!(&[0], sp.path());
+
+    let sp = g.sssp_dijkstra_any(0, &[6, 7]);
+    assert!(!sp.is_feasible());
+    assert_eq!((0, 6), (sp.src(), sp.dest()));
+    assert!(sp.path().is_empty());
+    assert!(!g.sssp_dijkstra_any(0, &[]).is_feasible());
+
+    // The source node is reachable from itself in all other searches as well.
+    let lsp = g.sssp_dijkstra_lazy(0);
+    assert!(lsp.get(0).is_feasible());
+    assert_eq!(&[0], lsp.get(0).path());
+    assert_eq!(0, lsp.get(0).dist());
+    assert_eq!(&[0], g.sssp_dijkstra(0, &[0])[0].path());
+}
+
+#[test]
+fn long_path() {
+    let n_hops = 50_000;
+    let g = SimpleGraph::<u32>::from_edges((0..n_hops).map(|ii| (ii, ii + 1, 2)));
+    let lsp = g.sssp_dijkstra_lazy(0);
+
+    let sp = lsp.get(n_hops);
+    assert_eq!(2 * n_hops as u32, sp.dist());
+    assert_eq!(n_hops, sp.hops());
+    assert_eq!((0..=n_hops).collect::<Vec<_>>(), sp.path());
+
+    let sp = &lsp.get_list(&[n_hops / 2])[0];
+    assert_eq!((0..=n_hops / 2).collect::<Vec<_>>(), sp.path());
+    assert!(sp.segments().iter().all(|(u, v, w)| *v == u + 1 && *w == 2));
+}
+
+#[test]
+fn edge_data() {
+    #[derive(Debug, PartialEq)]
+    struct Street {
+        name: &'static str,
+        lanes: u8,
+    }
+
+    let street = |name, lanes| Street { name, lanes };
+    let mut g = SimpleGraph::with_edge_data();
+    g.add_weighted_edge_with(0, 1, 4u32, street("Main Street", 2));
+    g.add_weighted_edge_with(1, 2, 3, street("Bridge Road", 4));
+    g.add_weighted_edge_with(0, 2, 9, street("Ring Road", 6));
+    g.add_weighted_edges(2, 3, 1);
+    g.add_weighted_edge_with(3, 3, 1, street("Roundabout", 1));
+
+    assert_eq!(4, g.n_nodes());
+    assert_eq!(Some(&street("Bridge Road", 4)), g.edge_data(2, 1));
+    assert_eq!(None, g.edge_data(2, 3));
+    assert_eq!(None, g.edge_data(3, 3));
+
+    let sp = &g.sssp_dijkstra(0, &[3])[0];
+    assert_eq!(8, sp.dist());
+    let route = sp.edge_data(&g);
+    assert_eq!(3, route.len());
+    assert_eq!(Some(&street("Main Street", 2)), route[0]);
+    assert_eq!(Some("Bridge Road"), route[1].map(|s| s.name));
+    assert_eq!(None, route[2]);
+
+    // A new edge between the same nodes replaces the payload.
+    g.add_weighted_edge_with(1, 0, 2, street("Main Street", 3));
+    assert_eq!(Some(3), g.edge_data(0, 1).map(|s| s.lanes));
+
+    let (edges, dist) = mst_prim_edges(&g, 0);
+    assert_eq!(6, dist);
+    assert_eq!(3, edges.len());
+
+    assert!(g.sssp_dijkstra_lazy(0).get(0).edge_data(&g).is_empty());
+}
+
+#[test]
+fn edge_data_transforms() {
+    let mut g = SimpleGraph::with_edge_data();
+    g.add_weighted_edge_with(0, 1, 4u32, "a");
+    g.add_weighted_edge_with(1, 2, 3, "b");
+    g.add_weighted_edge_with(0, 2, 9, "c");
+    g.add_weighted_arc(2, 3, 1);
+
+    let rev = g.reverse();
+    assert_eq!(Some(&"b"), rev.edge_data(2, 1));
+    assert_eq!(Some(&1), rev.edge_weight(3, 2));
+
+    let und = g.to_undirected();
+    assert_eq!(Some(&"c"), und.edge_data(0, 2));
+    assert_eq!(Some(&1), und.edge_weight(3, 2));
+
+    let (mst, dist) = mst_prim(&g, 0);
+    assert_eq!(8, dist);
+    assert_eq!(Some(&"a"), mst.edge_data(0, 1));
+    assert_eq!(Some(&"b"), mst.edge_data(1, 2));
+    assert_eq!(None, mst.edge_data(2, 3));
+    assert!(!mst.has_edge(0, 2));
+
+    let forest = mst_forest(&g);
+    assert_eq!(1, forest.len());
+    assert_eq!(Some(&"b"), forest[0].0.edge_data(2, 1));
+
+    // Payloads of self win over those of other, missing ones are copied.
+    let mut other = SimpleGraph::with_edge_data();
+    other.add_weighted_edge_with(0, 1, 2, "x");
+    other.add_weighted_edge_with(3, 4, 5, "y");
+
+    let mut merged = g.reverse();
+    merged.merge_from(&other);
+    assert_eq!(Some(&2), merged.edge_weight(0, 1));
+    assert_eq!(Some(&"a"), merged.edge_data(0, 1));
+    assert_eq!(Some(&"y"), merged.edge_data(4, 3));
+
+    let merged = g.merge(other);
+    assert_eq!(Some(&"a"), merged.edge_data(1, 0));
+    assert_eq!(Some(&"y"), merged.edge_data(3, 4));
+    assert_eq!(5, merged.n_nodes());
+}
+
+#[test]
+fn labeled_graph() {
+    let mut g = LabeledGraph::new();
+    g.add_weighted_edges("Frankfurt", "Mannheim", 85);
+    g.add_weighted_edges("Frankfurt", "Wuerzburg", 217);
+    g.add_weighted_edges("Frankfurt", "Kassel", 173);
+    g.add_weighted_edges("Mannheim", "Karlsruhe", 80);
+    g.add_weighted_edges("Wuerzburg", "Erfurt", 186);
+    g.add_weighted_edges("Wuerzburg", "Nuremberg", 103);
+    g.add_weighted_edges("Karlsruhe", "Augsburg", 250);
+    g.add_weighted_edges("Nuremberg", "Stuttgart", 183);
+    g.add_weighted_edges("Kassel", "Munich", 502);
+    g.add_weighted_edges("Augsburg", "Munich", 84);
+    g.add_weighted_edges("Nuremberg", "Munich", 167);
+    assert_eq!(Some(&"Munich"), g.label(9));
+    assert_eq!(0, g.add_node("Frankfurt"));
+    assert_eq!(10, g.add_node("Berlin"));
+
+    assert_eq!(11, g.n_nodes());
+    assert_eq!(22, g.n_edges());
+    assert_eq!(Some(2), g.index("Wuerzburg"));
+    assert_eq!(None, g.index("Hamburg"));
+    assert_eq!(Some(&84), g.edge_weight("Munich", "Augsburg"));
+    assert_eq!(None, g.edge_weight("Munich", "Berlin"));
+
+    let sp = g.sssp_dijkstra("Frankfurt", &["Munich", "Berlin", "Hamburg"]);
+    assert_eq!(487, sp[0].dist());
+    assert_eq!(
+        vec![&"Frankfurt", &"Wuerzburg", &"Nuremberg", &"Munich"],
+        g.path_labels(&sp[0])
+    );
+    assert!(!sp[1].is_feasible());
+    assert!(!sp[2].is_feasible());
+    assert!(g.path_labels(&sp[2]).is_empty());
+
+    // The same as on the underlying graph.
+    let (edges, dist) = g.mst_prim("Frankfurt");
+    let (expected, expected_dist) = mst_prim_edges(g.graph(), 0);
+    assert_eq!(expected_dist, dist);
+    assert_eq!(expected.len(), edges.len());
+    for ((node1, node2, w), (label1, label2, weight)) in expected.into_iter().zip(edges) {
+        assert_eq!(
+            (g.label(node1), g.label(node2)),
+            (Some(label1), Some(label2))
+        );
+        assert_eq!(w, weight);
+    }
+
+    let g = LabeledGraph::<String, u32>::default();
+    assert_eq!(None, g.index("Frankfurt"));
+}
+
+#[test]
+fn to_undirected() {
+    let mut g = SimpleGraph::<u32>::new();
+    g.add_weighted_arc(0, 1, 4);
+    g.add_weighted_arc(1, 2, 3);
+    g.add_weighted_edges(2, 3, 5);
+    g.add_node(4);
+    assert!(!g.has_edge(1, 0));
+
+    let ug = g.to_undirected();
+    assert_eq!(5, ug.n_nodes());
+    assert_eq!(6, ug.n_edges());
+    assert_eq!(g.total_weight(), ug.total_weight());
+
+    for &(node1, node2, w) in &[(0, 1, 4), (1, 2, 3), (2, 3, 5)] {
+        assert_eq!(Some(&w), ug.edge_weight(node1, node2));
+        assert_eq!(Some(&w), ug.edge_weight(node2, node1));
+    }
+
+    assert_eq!(Some(12), ug.sssp_dijkstra_lazy(3).dist(0));
+}
+
+#[test]
+fn reverse() {
+    let mut g = SimpleGraph::<u32>::new();
+    g.add_weighted_arc(0, 1, 4);
+    g.add_weighted_arc(0, 2, 1);
+    g.add_weighted_arc(2, 1, 2);
+    g.add_weighted_edges(1, 3, 5);
+    g.add_node(4);
+
+    let rg = g.reverse();
+    assert_eq!(g.n_nodes(), rg.n_nodes());
+    assert_eq!(g.n_edges(), rg.n_edges());
+    assert_eq!(Some(&4), rg.edge_weight(1, 0));
+    assert!(!rg.has_edge(0, 1));
+    assert_eq!(Some(&5), rg.edge_weight(1, 3));
+    assert_eq!(Some(&5), rg.edge_weight(3, 1));
+
+    // In-degrees of the original graph.
+    let in_degrees: Vec<usize> = (0..5).map(|node| rg.degree(node)).collect();
+    assert_eq!(vec![0, 3, 1, 1, 0], in_degrees);
+
+    // A backward search gives the distances to a node.
+    let lsp = rg.sssp_dijkstra_lazy(3);
+    for node in 0..4 {
+        assert_eq!(g.sssp_dijkstra_lazy(node).dist(3), lsp.dist(node));
+    }
+    assert_eq!(None, lsp.dist(4));
+
+    // An undirected graph is its own reverse.
+    let ug = g.to_undirected();
+    let rug = ug.reverse();
+    for node1 in 0..5 {
+        for node2 in 0..5 {
+            assert_eq!(ug.edge_weight(node1, node2), rug.edge_weight(node1, node2));
+        }
+    }
+}
+
+#[test]
+fn graph_errors() {
+    let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7), (1, 2, 3), (4, 5, 1)]);
+
+    assert_eq!(10, g.try_sssp_dijkstra(0, &[2]).unwrap()[0].dist());
+    assert_eq!(
+        Err(GraphError::NodeNotFound(42)),
+        g.try_sssp_dijkstra(42, &[0]).map(|_| ())
+    );
+    assert_eq!(
+        Err(GraphError::NodeNotFound(3)),
+        g.try_sssp_dijkstra(0, &[2, 3]).map(|_| ())
+    );
+    assert_eq!(
+        Err(GraphError::NodeNotFound(42)),
+        try_mst_prim(&g, 42).map(|_| ())
+    );
+    assert_eq!(11, try_mst_prim(&g, 0).unwrap().1);
+
+    let empty = SimpleGraph::<u32>::new();
+    assert_eq!(
+        Err(GraphError::EmptyGraph),
+        empty.try_sssp_dijkstra(0, &[]).map(|_| ())
+    );
+    assert_eq!(
+        Err(GraphError::EmptyGraph),
+        try_mst_prim(&empty, 0).map(|_| ())
+    );
+
+    assert_eq!(
+        "node 42 is not in the graph",
+        GraphError::NodeNotFound(42).to_string()
+    );
+}
+
+#[test]
+fn add_edges_checked() {
+    let mut g = SimpleGraph::<f64>::new();
+
+    assert_eq!(Ok(()), g.add_weighted_edges_checked(0, 1, 1.5));
+    assert_eq!(
+        Err(GraphError::SelfLoop(2)),
+        g.add_weighted_edges_checked(2, 2, 1.0)
+    );
+    assert_eq!(
+        Err(GraphError::InvalidWeight(1, 2)),
+        g.add_weighted_edges_checked(1, 2, f64::NAN)
+    );
+
+    // Rejected edges leave the graph untouched.
+    assert_eq!(2, g.n_nodes());
+    assert_eq!(2, g.n_edges());
+    assert!(!g.has_node(2));
+    assert_eq!(Some(&1.5), g.edge_weight(1, 0));
+
+    assert_eq!(
+        "edge from node 2 to itself",
+        GraphError::SelfLoop(2).to_string()
+    );
+}
+
+#[test]
+fn float_weights() {
+    let mut g = SimpleGraph::<f64>::new();
+    g.add_weighted_edges(0, 1, 0.25);
+    g.add_weighted_edges(1, 2, 0.5);
+    g.add_weighted_edges(0, 2, 0.8);
+    g.add_weighted_edges(2, 3, 1.125);
+    g.add_weighted_edges(3, 4, f64::INFINITY);
+    g.add_weighted_edges(4, 5, f64::MAX);
+    g.add_weighted_edges(5, 6, f64::MAX);
+
+    let sp = &g.sssp_dijkstra(0, &[3])[0];
+    assert!(sp.is_feasible());
+    assert_eq!(1.875, sp.dist());
+    assert_eq!(&[0, 1, 2, 3], sp.path());
+
+    // An infinite weight behaves like a missing edge.
+    let lsp = g.sssp_dijkstra_lazy(0);
+    assert!(!lsp.get(4).is_feasible());
+    assert_eq!(None, lsp.dist(4));
+
+    // So does a distance that overflows to infinity.
+    let lsp = g.sssp_dijkstra_lazy(4);
+    assert_eq!(Some(f64::MAX), lsp.dist(5));
+    assert!(!lsp.get(6).is_feasible());
+
+    // Prim's algorithm ignores the edges with a weight of at least f64::MAX.
+    let (mst, weight) = mst_prim(&g, 0);
+    assert_eq!(0.25 + 0.5 + 1.125, weight);
+    assert_eq!(4, mst.n_nodes());
+
+    let mut g = SimpleGraph::<f32>::new();
+    g.add_weighted_arc(0, 1, 0.1);
+    g.add_weighted_arc(1, 2, 0.2);
+    g.add_weighted_arc(0, 2, 0.4);
+    g.add_weighted_arc(2, 3, f32::INFINITY);
+    let dag = g.dag_shortest_path(0).unwrap();
+    assert_eq!(g.sssp_dijkstra_lazy(0).distances(), dag.distances());
+    assert_eq!(Some(0.1 + 0.2), dag.dist(2));
+    assert_eq!(None, dag.dist(3));
+}
+
+#[test]
+#[should_panic(expected = "the weight of the edge between 1 and 2 is not comparable, e.g. NaN")]
+fn nan_weight() {
+    let mut g = SimpleGraph::<f64>::new();
+    g.add_weighted_edges(0, 1, 1.0);
+    g.add_weighted_edges(1, 2, f64::NAN);
+}
+
+#[test]
+#[should_panic(expected = "source node 42 is not in the graph")]
+fn dijkstra_src_out_of_range() {
+    let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7)]);
+    g.sssp_dijkstra(42, &[0]);
+}
+
+#[test]
+#[should_panic(expected = "source node 42 is not in the graph")]
+fn dag_shortest_path_src_out_of_range() {
+    let mut g = SimpleGraph::<i32>::new();
+    g.add_weighted_arc(0, 1, -7);
+    let _ = g.dag_shortest_path(42);
+}
+
+#[test]
+fn dijkstra_sparse_ids() {
+    let g = SimpleGraph::<u32>::from_edges(vec![(0, 50, 1), (50, 7, 2)]);
+    assert_eq!(3, g.n_nodes());
+
+    let sp = &g.sssp_dijkstra(50, &[0, 7])[..];
+    assert_eq!((1, &[50, 0][..]), (sp[0].dist(), sp[0].path()));
+    assert_eq!((2, &[50, 7][..]), (sp[1].dist(), sp[1].path()));
+
+    let mut buffer = DijkstraBuffer::new();
+    let sp = g.sssp_dijkstra_with_buffer(0, &[7], &mut buffer);
+    assert_eq!(&[0, 50, 7], sp[0].path());
+
+    let lsp = g.sssp_dijkstra_lazy_with_strategy(7, DijkstraStrategy::Reinsert);
+    assert_eq!(Some(3), lsp.dist(0));
+
+    let lsp = g.sssp_dijkstra_within(0, 1);
+    assert!(lsp.is_reachable(50));
+    assert!(!lsp.is_reachable(7));
+}
+
+#[test]
+fn mst_prim_sparse_ids() {
+    use std::collections::HashMap;
+
+    // Node ids as obtained by hashing external ids down to u32s.
+    let id = |ii: u64| ((ii * 2_654_435_761) % (1 << 32)) as usize;
+    let mut edges = Vec::new();
+    for ii in 0..60u64 {
+        edges.push((id(ii), id(ii + 1), (ii * 7 % 11 + 1) as u32));
+        edges.push((id(ii), id((ii * 13 + 5) % 61), (ii * 5 % 17 + 1) as u32));
+    }
+
+    let g = SimpleGraph::from_edges_dedup(edges.clone());
+    let (mst, dist) = mst_prim(&g, id(0));
+    let (mst_edges, dist_edges) = mst_prim_edges(&g, id(30));
+
+    // Kruskal's algorithm with a union-find over the same edges.
+    fn find(parent: &mut HashMap<usize, usize>, node: usize) -> usize {
+        let p = *parent.entry(node).or_insert(node);
+        if p == node {
+            node
+        } else {
+            let root = find(parent, p);
+            parent.insert(node, root);
+            root
+        }
+    }
+
+    edges.sort_by_key(|(_, _, w)| *w);
+    let mut parent = HashMap::new();
+    let mut expected = 0;
+    for (node1, node2, w) in edges {
+        let (root1, root2) = (find(&mut parent, node1), find(&mut parent, node2));
+        if root1 != root2 {
+            parent.insert(root1, root2);
+            expected += w;
+        }
+    }
+
+    assert_eq!(expected, dist);
+    assert_eq!(expected, dist_edges);
+    assert_eq!(60, mst_edges.len());
+    assert_eq!(61, mst.n_nodes());
+}
+
+#[test]
+fn graph_parts() {
+    let g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 7), (1, 2, 3)]);
+    let (n_edges, mut weights) = g.into_parts();
+    assert_eq!(4, n_edges);
+    assert_eq!(vec![(1, 7)], weights[&0]);
+
+    let capacity = weights.capacity();
+    weights.clear();
+    weights.insert(0, vec![(5, 2)]);
+    weights.insert(5, vec![(0, 2)]);
+
+    let g = SimpleGraph::from_parts(2, weights);
+    assert_eq!(2, g.n_nodes());
+    assert_eq!(2, g.n_edges());
+    assert_eq!(Some(&2), g.edge_weight(5, 0));
+    assert_eq!(Some(2), g.sssp_dijkstra_lazy(0).dist(5));
+    assert_eq!(capacity, g.into_parts().1.capacity());
+}
+
+#[test]
+fn graph_parts_arcs() {
+    let mut g = SimpleGraph::<u32>::new();
+    g.add_weighted_arc(0, 1, 4);
+    g.add_weighted_arc(1, 2, 3);
+
+    let (n_edges, weights) = g.into_parts();
+    assert_eq!(Some(&Vec::new()), weights.get(&2));
+    let g = SimpleGraph::from_parts(n_edges, weights);
+    assert_eq!(3, g.n_nodes());
+    assert_eq!(Some(7), g.sssp_dijkstra_lazy(0).dist(2));
+
+    // The head of an arc is added as a node, even if its list is missing in the map.
+    let (_, mut weights) = g.into_parts();
+    weights.clear();
+    weights.insert(0, vec![(5, 1)]);
+
+    let g = SimpleGraph::from_parts(1, weights);
+    assert!(g.has_node(5));
+    assert_eq!(0, g.degree(5));
+    assert_eq!(1, g.sssp_dijkstra(0, &[5])[0].dist());
+    assert!(g.is_connected());
+    assert_eq!(Some(1), g.sssp_bfs(0).dist(5));
+    assert_eq!(1, mst_prim(&g, 0).1);
+}
+
+#[test]
+#[should_panic(expected = "the weight of the edge between 1 and 0 is not comparable")]
+fn graph_parts_nan() {
+    let mut weights = std::collections::HashMap::new();
+    weights.insert(1, vec![(0, f64::NAN)]);
+    SimpleGraph::from_parts(1, weights);
+}
+
+#[test]
+fn mst_prim_deterministic() {
+    use crate::graph::generate::grid_2d;
+
+    // A grid with many equal weights has many minimum spanning trees.
+    let grid = grid_2d(8, 8, |node1, node2| 1 + (node1 * node2) % 3);
+    let (indptr, indices, weights) = grid.to_csr();
+    let mut edges = Vec::new();
+    for node1 in 0..64 {
+        for ii in indptr[node1]..indptr[node1 + 1] {
+            if node1 < indices[ii] {
+                edges.push((node1, indices[ii], weights[ii]));
+            }
+        }
+    }
+
+    let sorted_edges = |g: &SimpleGraph<usize>| {
+        let (mut mst, dist) = mst_prim_edges(g, 0);
+        for edge in &mut mst {
+            *edge = (edge.0.min(edge.1), edge.0.max(edge.1), edge.2);
+        }
+        mst.sort_unstable();
+        (mst, dist)
+    };
+
+    // The same edges added in a different order yield the same tree.
+    let expected = sorted_edges(&SimpleGraph::from_edges(edges.iter().copied()));
+    assert_eq!(63, expected.0.len());
+    assert_eq!(
+        expected,
+        sorted_edges(&SimpleGraph::from_edges(edges.iter().copied()))
+    );
+    assert_eq!(
+        expected,
+        sorted_edges(&SimpleGraph::from_edges(edges.iter().rev().copied()))
+    );
+
+    let mut g = SimpleGraph::from_edges(edges.iter().map(|(n1, n2, w)| (*n2, *n1, *w)).rev());
+    assert_eq!(expected, sorted_edges(&g));
+    g.finalize();
+    assert_eq!(expected, sorted_edges(&g));
+}
+
+#[test]
+fn mst_forest_components() {
+    let mut g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+    ]);
+    g.add_weighted_edges(6, 7, 2);
+    g.add_weighted_edges(6, 8, 3);
+    g.add_node(10);
+
+    let forest = mst_forest(&g);
+    assert_eq!(3, forest.len());
+
+    let (tree, dist) = &forest[0];
+    assert_eq!(33, *dist);
+    assert_eq!(6, tree.n_nodes());
+    assert_eq!(10, tree.n_edges());
+    assert!(tree.has_edge(2, 5) && tree.has_edge(4, 5) && !tree.has_edge(1, 3));
+
+    let (tree, dist) = &forest[1];
+    assert_eq!(5, *dist);
+    assert_eq!(3, tree.n_nodes());
+    assert!(tree.has_edge(6, 7) && tree.has_edge(6, 8));
+
+    let (tree, dist) = &forest[2];
+    assert_eq!(0, *dist);
+    assert!(tree.has_node(10));
+    assert_eq!(0, tree.n_edges());
+
+    assert_eq!(38, mst_prim(&g, 0).1);
+    assert!(mst_forest(&SimpleGraph::<u32>::new()).is_empty());
+}
+
+#[test]
+fn shortest_paths_dispatch() {
+    let mut g = SimpleGraph::<i32>::new();
+    g.add_weighted_arc(0, 1, 4);
+    g.add_weighted_arc(0, 2, 1);
+    g.add_weighted_arc(2, 1, 2);
+    assert!(!g.has_negative_edge());
+    assert_eq!(Some(3), g.shortest_paths(0).unwrap().dist(1));
+
+    // Dijkstra settles node 1 before the cheaper detour over node 3 is found.
+    g.add_weighted_arc(0, 3, 5);
+    g.add_weighted_arc(3, 1, -4);
+    assert!(g.has_negative_edge());
+    let lsp = g.shortest_paths(0).unwrap();
+    assert_eq!(Some(1), lsp.dist(1));
+    assert_eq!(&[0, 3, 1], lsp.get(1).path());
+
+    g.add_weighted_edges(4, 5, -1);
+    assert!(g.shortest_paths(4).is_err());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn sample_pop() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut ph = PairingHeap::<i32, u32>::new();
+    assert_eq!(None, ph.sample_pop(&mut rng));
+
+    for ii in 0..100 {
+        ph.insert(ii, ii as u32 + 1);
+    }
+
+    let mut popped = Vec::new();
+    while let Some((key, prio)) = ph.sample_pop(&mut rng) {
+        assert_eq!(key as u32 + 1, prio);
+        assert!(ph.is_valid());
+        popped.push(key);
+    }
+    popped.sort_unstable();
+    assert_eq!((0..100).collect::<Vec<_>>(), popped);
+
+    // Key 0 has priority 1 and is picked about nine times as often as key 1 with priority 9.
+    let mut counts = [0; 2];
+    for _ in 0..10_000 {
+        ph.insert(0, 1);
+        ph.insert(1, 9);
+        counts[ph.sample_pop(&mut rng).unwrap().0 as usize] += 1;
+        ph.delete_min();
+    }
+    assert!(counts[0] > 8_500 && counts[0] < 9_500);
+
+    // Without any positive priority, the minimum element is removed.
+    ph.insert(5, 0);
+    ph.insert(6, 0);
+    assert!(ph.sample_pop(&mut rng).is_some());
+    assert_eq!(1, ph.len());
+}
+
+#[test]
+fn rebuild() {
+    let mut ph = PairingHeap::from_sorted((0..1000).map(|ii| (ii, ii)).collect());
+    assert_eq!(1000, ph.max_depth());
+
+    ph.rebuild();
+    assert!(ph.max_depth() <= 11);
+    assert_eq!(1000, ph.len());
+    assert!(ph.is_valid());
+
+    for ii in 0..1000 {
+        assert_eq!(Some((ii, ii)), ph.delete_min());
+    }
+
+    // Handles to the elements stay valid.
+    let (mut ph, elmts) = create_heap(0, 100);
+    ph.rebuild();
+    ph.update_prio(&elmts[50], -1);
+    assert_eq!(Some((50, -1)), ph.delete_min());
+    assert!(ph.is_valid());
+
+    let mut ph = PairingHeap::<i32, i32>::new();
+    ph.rebuild();
+    assert!(ph.is_empty());
+}
+
+#[test]
+fn remove_key() {
+    let mut ph = PairingHeap::<i32, i32>::new();
+    for ii in 0..50 {
+        ph.insert(ii % 10, ii);
+    }
+    for _ in 0..3 {
+        ph.delete_min();
+    }
+
+    let mut removed = ph.remove_key(&7);
+    removed.sort_unstable();
+    assert_eq!(vec![7, 17, 27, 37, 47], removed);
+    assert_eq!(42, ph.len());
+    assert!(ph.is_valid());
+
+    assert!(ph.remove_key(&7).is_empty());
+    let mut removed = ph.remove_key(&1);
+    removed.sort_unstable();
+    assert_eq!(vec![11, 21, 31, 41], removed);
+    assert_eq!(38, ph.len());
+
+    let mut prev = i32::MIN;
+    while let Some((key, prio)) = ph.delete_min() {
+        assert!(key != 7 && key != 1);
+        assert!(prio >= prev);
+        prev = prio;
+    }
+}
+
+#[test]
+fn sssp_bfs() {
+    use crate::graph::generate::erdos_renyi;
+
+    // An unweighted graph with the same edges as a random graph.
+    let weighted = erdos_renyi::<u32>(300, 0.01, 1..10, 5);
+    let mut g = SimpleGraph::<u32>::new();
+    for node1 in 0..300 {
+        g.add_node(node1);
+        for (node2, _) in weighted.neighbours(node1) {
+            if node1 < node2 {
+                g.add_edge(node1, node2);
+            }
+        }
+    }
+    assert_eq!(weighted.n_edges(), g.n_edges());
+    assert_eq!(
+        Some(&1),
+        g.edge_weight(g.neighbours(0).next().unwrap().0, 0)
+    );
+
+    for src in [0, 17, 299] {
+        let bfs = g.sssp_bfs(src);
+        let dijkstra = g.sssp_dijkstra_lazy(src);
+
+        for node in 0..300 {
+            assert_eq!(dijkstra.dist(node).map(|d| d as usize), bfs.dist(node));
+
+            let sp = bfs.get(node);
+            assert_eq!(dijkstra.get(node).is_feasible(), sp.is_feasible());
+            if sp.is_feasible() {
+                assert_eq!(sp.dist(), sp.hops());
+                assert_eq!(src, sp.path()[0]);
+            }
+        }
+    }
+}
+
+#[test]
+fn reachability() {
+    let mut g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (1, 2, 1), (3, 4, 1)]);
+    g.add_weighted_arc(4, 5, 1);
+    g.add_node(7);
+
+    assert!(g.is_reachable(0, 2));
+    assert!(g.is_reachable(2, 0));
+    assert!(g.is_reachable(7, 7));
+    assert!(!g.is_reachable(0, 3));
+    assert!(g.is_reachable(3, 5));
+    assert!(!g.is_reachable(5, 3));
+    assert!(!g.is_reachable(6, 6));
+    assert!(!g.is_reachable(0, 100));
+
+    let mut set: Vec<_> = g.reachable_set(3).into_iter().collect();
+    set.sort_unstable();
+    assert_eq!(vec![3, 4, 5], set);
+    assert_eq!(1, g.reachable_set(7).len());
+    assert!(g.reachable_set(6).is_empty());
+}
+
+#[test]
+fn degree_hint() {
+    let mut g = SimpleGraph::<u32>::with_degree_hint(10, 8);
+    g.add_node(9);
+    for ii in 0..5 {
+        g.add_weighted_edges(ii, ii + 1, 1);
+    }
+    g.reserve_edges_for(7, 2);
+
+    assert_eq!(8, g.n_nodes());
+    assert_eq!(10, g.n_edges());
+    assert_eq!(5, g.sssp_dijkstra(0, &[5])[0].dist());
+
+    let (_, weights) = g.into_parts();
+    for nb in weights.values() {
+        assert!(nb.capacity() >= 8);
+    }
+}
+
+#[test]
+fn shortest_path_dag() {
+    // A 3x3 grid with unit weights and a detour to node 8.
+    let mut g = SimpleGraph::<u32>::new();
+    for row in 0..3 {
+        for col in 0..3 {
+            let node = row * 3 + col;
+            if col < 2 {
+                g.add_weighted_edges(node, node + 1, 1);
+            }
+            if row < 2 {
+                g.add_weighted_edges(node, node + 3, 1);
+            }
+        }
+    }
+    g.add_weighted_edges(0, 8, 5);
+    g.add_weighted_edges(0, 1, 1);
+    g.add_node(10);
+
+    let dag = g.shortest_path_dag(0);
+    assert_eq!(8, dag.len());
+    assert!(!dag.contains_key(&0));
+    assert!(!dag.contains_key(&10));
+    assert_eq!(&vec![0], &dag[&1]);
+    assert_eq!(&vec![1, 3], &dag[&4]);
+    assert_eq!(&vec![5, 7], &dag[&8]);
+
+    // Counts the shortest paths to each node in order of distance.
+    let lsp = g.sssp_dijkstra_lazy(0);
+    let mut nodes: Vec<usize> = dag.keys().copied().collect();
+    nodes.sort_unstable_by_key(|node| lsp.dist(*node));
+
+    let mut count = vec![0; 9];
+    count[0] = 1;
+    for node in nodes {
+        count[node] = dag[&node].iter().map(|pred| count[*pred]).sum();
+    }
+    assert_eq!(vec![1, 1, 1, 1, 2, 3, 1, 3, 6], count);
+}
+
+#[cfg(feature = "instrument")]
+#[test]
+fn op_counts() {
+    use crate::OpCounts;
+
+    let mut ph = PairingHeap::<i32, i32>::new_instrumented();
+    assert_eq!(Some(OpCounts::default()), ph.op_counts());
+
+    for ii in 0..10 {
+        ph.insert(ii, ii);
+    }
+    let counts = ph.op_counts().unwrap();
+    assert_eq!(10, counts.allocations);
+    assert_eq!(9, counts.melds);
+    assert_eq!(9, counts.comparisons);
+
+    // The nine children of the root are paired up in two passes with four melds each.
+    ph.delete_min();
+    let counts = ph.op_counts().unwrap();
+    assert_eq!(10, counts.allocations);
+    assert_eq!(17, counts.melds);
+    assert_eq!(17, counts.comparisons);
+
+    assert_eq!(None, PairingHeap::<i32, i32>::new().op_counts());
+}
+
+#[test]
+fn eccentricity() {
+    let mut g = SimpleGraph::<u32>::from_edges(vec![
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 5, 2),
+        (2, 3, 11),
+        (3, 4, 6),
+        (4, 5, 9),
+    ]);
+
+    let eccs: Vec<_> = (0..6).map(|node| g.eccentricity(node)).collect();
+    assert_eq!(
+        vec![Some(20), Some(21), Some(11), Some(20), Some(21), Some(13)],
+        eccs
+    );
+    assert_eq!(None, g.eccentricity(6));
+    assert_eq!(Some(21), g.diameter());
+    assert_eq!(Some(11), g.radius());
+
+    #[cfg(feature = "rayon")]
+    {
+        assert_eq!(Some(21), g.diameter_par());
+        assert_eq!(Some(11), g.radius_par());
+    }
+
+    g.add_weighted_edges(6, 7, 1);
+    assert_eq!(None, g.eccentricity(0));
+    assert_eq!(None, g.diameter());
+    assert_eq!(None, g.radius());
+
+    // In a directed graph, only some nodes may reach all others.
+    let mut d = SimpleGraph::<u32>::new();
+    d.add_weighted_arc(0, 1, 2);
+    d.add_weighted_arc(1, 2, 3);
+    assert_eq!(None, d.diameter());
+    assert_eq!(Some(5), d.radius());
+    assert_eq!(None, SimpleGraph::<u32>::new().diameter());
+}
+
+#[test]
+fn indexed_heap() {
+    use crate::IndexedPairingHeap;
+
+    let mut heap = IndexedPairingHeap::<String, i32>::new_indexed();
+    for ii in 0..20 {
+        assert_eq!(None, heap.insert(ii.to_string(), 100 + ii));
+    }
+    assert_eq!(20, heap.len());
+
+    assert_eq!(Some(110), heap.decrease_prio_indexed("10", 5));
+    assert_eq!(Some(119), heap.decrease_prio_indexed("19", 1));
+    assert_eq!(Some(100), heap.insert("0".to_string(), 200));
+    assert_eq!(None, heap.decrease_prio_indexed("20", 0));
+    assert_eq!(20, heap.len());
+    assert_eq!(Some(&200), heap.get_prio("0"));
+
+    assert_eq!(Some((&"19".to_string(), &1)), heap.find_min());
+    assert_eq!(Some(("19".to_string(), 1)), heap.delete_min());
+    assert_eq!(Some(("10".to_string(), 5)), heap.delete_min());
+    assert!(!heap.contains_key("10"));
+    assert!(heap.contains_key("0"));
+
+    let mut prev = i32::MIN;
+    let mut count = 0;
+    while let Some((key, prio)) = heap.delete_min() {
+        assert!(prio >= prev);
+        assert!(!heap.contains_key(&key));
+        prev = prio;
+        count += 1;
+    }
+    assert_eq!(18, count);
+    assert_eq!(200, prev);
+}
+
+#[test]
+fn betweenness_centrality() {
+    let path = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (1, 2, 1), (2, 3, 1)]);
+    assert_eq!(vec![0.0, 4.0, 4.0, 0.0], path.betweenness_centrality());
+
+    // A cycle of four nodes, in which the opposite nodes are connected by two shortest paths each. The
+    // chord between 0 and 3 is longer than both.
+    let mut g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (1, 3, 2), (0, 2, 2), (2, 3, 1)]);
+    g.add_weighted_edges(0, 3, 4);
+    g.add_node(5);
+    assert_eq!(
+        vec![1.0, 1.0, 1.0, 1.0, 0.0, 0.0],
+        g.betweenness_centrality()
+    );
+
+    // In a directed cycle, every node lies on the paths of the pairs that it separates.
+    let mut c = SimpleGraph::<u32>::new();
+    c.add_weighted_arc(0, 1, 1);
+    c.add_weighted_arc(1, 2, 1);
+    c.add_weighted_arc(2, 0, 1);
+    assert_eq!(vec![1.0, 1.0, 1.0], c.betweenness_centrality());
+}
+
+#[test]
+fn pagerank() {
+    let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-6);
+
+    // Without damping, the stationary distribution of an undirected graph is proportional to the total
+    // weight of the edges at each node.
+    let mut g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (1, 2, 2), (0, 2, 3)]);
+    let ranks = g.pagerank(1.0, 1e-12, 1000);
+    assert!(close(&[4.0 / 12.0, 3.0 / 12.0, 5.0 / 12.0], &ranks));
+
+    g.add_node(4);
+    let ranks = g.pagerank(0.85, 1e-12, 1000);
+    assert_eq!(5, ranks.len());
+    assert_eq!(0.0, ranks[3]);
+    assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+    // Node 1 is dangling, its score is spread over both nodes.
+    let mut d = SimpleGraph::<u32>::new();
+    d.add_weighted_arc(0, 1, 1);
+    let ranks = d.pagerank(0.85, 1e-12, 1000);
+    assert!(close(&[0.5 / 1.425, 1.0 - 0.5 / 1.425], &ranks));
+
+    // The iteration stops after max_iter steps.
+    assert_eq!(vec![0.5, 0.5], d.pagerank(0.85, 1e-12, 0));
+    assert!(SimpleGraph::<u32>::new()
+        .pagerank(0.85, 1e-9, 10)
+        .is_empty());
+}
+
+#[test]
+fn closeness_centrality() {
+    let mut g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (1, 2, 1), (2, 3, 2), (5, 6, 4)]);
+    g.add_node(7);
+
+    assert_eq!(1.0 / 7.0, g.closeness_centrality(0));
+    assert_eq!(1.0 / 5.0, g.closeness_centrality(1));
+    assert_eq!(1.0 / 4.0, g.closeness_centrality(5));
+    assert_eq!(0.0, g.closeness_centrality(7));
+    assert_eq!(0.0, g.closeness_centrality(4));
+
+    assert_eq!(
+        vec![
+            1.0 / 7.0,
+            1.0 / 5.0,
+            1.0 / 5.0,
+            1.0 / 9.0,
+            0.0,
+            1.0 / 4.0,
+            1.0 / 4.0,
+            0.0
+        ],
+        g.closeness_centrality_all()
+    );
+}
+
+#[test]
+fn clustering_coefficient() {
+    let triangle = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+    assert_eq!(1.0, triangle.clustering_coefficient(0));
+    assert_eq!(1.0, triangle.average_clustering());
+
+    let star = SimpleGraph::<u32>::from_edges((1..5).map(|ii| (0, ii, 1)));
+    assert_eq!(0.0, star.clustering_coefficient(0));
+    assert_eq!(0.0, star.clustering_coefficient(1));
+    assert_eq!(0.0, star.average_clustering());
+
+    // A triangle with a pendant node and a parallel edge.
+    let mut g = SimpleGraph::<u32>::from_edges(vec![(0, 1, 1), (1, 2, 1), (2, 0, 1), (0, 3, 1)]);
+    g.add_weighted_arc(1, 2, 5);
+    assert_eq!(1.0 / 3.0, g.clustering_coefficient(0));
+    assert_eq!(1.0, g.clustering_coefficient(1));
+    assert_eq!(0.0, g.clustering_coefficient(3));
+    assert_eq!(0.0, g.clustering_coefficient(9));
+    assert!((g.average_clustering() - 7.0 / 12.0).abs() < 1e-12);
+
+    assert_eq!(0.0, SimpleGraph::<u32>::new().average_clustering());
+}
+
+#[test]
+fn replace_min() {
+    for strategy in [DeleteStrategy::TwoPass, DeleteStrategy::Multipass] {
+        let mut ph = PairingHeap::<i32, i32>::with_delete_strategy(strategy);
+        let mut reference = PairingHeap::<i32, i32>::new();
+        for ii in 0..100 {
+            ph.insert(ii, (ii * 37) % 101);
+            reference.insert(ii, (ii * 37) % 101);
+        }
+
+        // Keeps the 100 largest of a stream of priorities.
+        for ii in 100..300 {
+            let prio = (ii * 37) % 101 + ii / 3;
+            if prio > *ph.find_min().unwrap().1 {
+                let expected = reference.delete_min().map(|(_, p)| p);
+                reference.insert(ii, prio);
+                assert_eq!(expected, ph.replace_min(ii, prio).map(|(_, p)| p));
+            }
+        }
+
+        assert_eq!(100, ph.len());
+        assert!(ph.is_valid());
+        while let Some((_, prio)) = reference.delete_min() {
+            assert_eq!(Some(prio), ph.delete_min().map(|(_, p)| p));
+        }
+    }
+
+    // A new minimum replaces the old one as well.
+    let mut ph = StablePairingHeap::new_stable();
+    ph.insert("a", 1);
+    ph.insert("b", 2);
+    assert_eq!(Some(("a", 1)), ph.replace_min("c", 0));
+    assert_eq!(Some(("c", 0)), ph.replace_min("d", 2));
+    assert_eq!(Some(("b", 2)), ph.delete_min());
+    assert_eq!(Some(("d", 2)), ph.delete_min());
+    assert!(ph.is_empty());
+}
+
+#[test]
+fn priority_histogram() {
+    let mut ph = PairingHeap::<i32, i32>::new();
+    assert!(ph.priority_histogram().is_empty());
+
+    for ii in 0..100 {
+        ph.insert(ii, ii % 7);
+    }
+    ph.delete_min();
+    ph.decrease_prio(&50, 10);
+
+    let histogram = ph.priority_histogram();
+    assert_eq!(8, histogram.len());
+    assert_eq!(Some(&14), histogram.get(&0));
+    assert_eq!(Some(&1), histogram.get(&-9));
+    assert_eq!(Some(&14), histogram.get(&1));
+    assert_eq!(99, histogram.values().sum::<usize>());
+    assert_eq!(Some(&-9), histogram.keys().next());
+
+    // The heap is left untouched.
+    assert_eq!(99, ph.len());
+    assert!(ph.is_valid());
+}
+
+#[test]
+fn push_pop() {
+    let mut ph = PairingHeap::<i32, i32>::new();
+    let mut reference = PairingHeap::<i32, i32>::new();
+    for ii in 0..50 {
+        ph.insert(ii, (ii * 37) % 101);
+        reference.insert(ii, (ii * 37) % 101);
+    }
+
+    for ii in 50..300 {
+        let prio = (ii * 37) % 101 + ii / 5;
+        reference.insert(ii, prio);
+        let expected = reference.delete_min().map(|(_, p)| p);
+        assert_eq!(expected, Some(ph.push_pop(ii, prio).1));
+    }
+
+    assert_eq!(50, ph.len());
+    assert!(ph.is_valid());
+
+    // An element at most the minimum is returned right away, ...
+    let mut ph = PairingHeap::new();
+    assert_eq!(("a", 1), ph.push_pop("a", 1));
+    assert!(ph.is_empty());
+    ph.insert("b", 2);
+    assert_eq!(("c", 2), ph.push_pop("c", 2));
+    assert_eq!(1, ph.len());
+
+    // ... except for ties in a stable heap, where the older element comes first.
+    let mut ph = StablePairingHeap::new_stable();
+    ph.insert("b", 2);
+    assert_eq!(("b", 2), ph.push_pop("c", 2));
+    assert_eq!(Some(("c", 2)), ph.delete_min());
+}
+
+#[test]
+fn from_grid() {
+    let costs = vec![
+        vec![Some(1u32), Some(5), Some(1)],
+        vec![Some(1), None, Some(1)],
+        vec![Some(2), Some(1), Some(1)],
+    ];
+
+    // Moves are weighted by the cost of the destination cell.
+    let g = SimpleGraph::from_grid(&costs, false);
+    assert_eq!(9, g.n_nodes());
+    assert_eq!(0, g.degree(grid_index(1, 1, 3)));
+    assert_eq!(Some(&5), g.edge_weight(0, 1));
+    assert_eq!(Some(&1), g.edge_weight(1, 0));
+    assert_eq!(5, g.sssp_dijkstra(0, &[8])[0].dist());
+    assert_eq!(5, g.sssp_dijkstra(8, &[0])[0].dist());
+
+    // Diagonal moves may not cut across the impassable centre.
+    let g = SimpleGraph::from_grid(&costs, true);
+    assert!(!g.has_edge(grid_index(0, 1, 3), grid_index(1, 0, 3)));
+    assert!(!g.has_edge(0, 8));
+    assert_eq!(2, g.degree(0));
+    assert!(!g.has_edge(grid_index(2, 1, 3), grid_index(1, 2, 3)));
+
+    let g = SimpleGraph::from_grid(&[vec![Some(1u32), Some(2)], vec![Some(3), Some(4)]], true);
+    assert_eq!(Some(&4), g.edge_weight(0, 3));
+    assert_eq!(Some(&2), g.edge_weight(2, 1));
+    assert_eq!(4, g.sssp_dijkstra(0, &[3])[0].dist());
+
+    let g = SimpleGraph::<u32>::from_grid(&[], true);
+    assert_eq!(0, g.n_nodes());
+}
+
+#[test]
+fn generators() {
+    use crate::graph::generate::{erdos_renyi, grid_2d, random_geometric};
+
+    let g = erdos_renyi::<u32>(2_000, 0.005, 1..10, 7);
+    let h = erdos_renyi::<u32>(2_000, 0.005, 1..10, 7);
+    assert_eq!(2_000, g.n_nodes());
+    assert_eq!(g.to_csr(), h.to_csr());
+    assert_ne!(
+        g.to_csr(),
+        erdos_renyi::<u32>(2_000, 0.005, 1..10, 8).to_csr()
+    );
+    // About 10000 edges are expected, each stored in both directions.
+    assert!((18_000..22_000).contains(&g.n_edges()));
+    for node in 0..2_000 {
+        for (u, w) in g.neighbours(node) {
+            assert_ne!(node, u);
+            assert!((1..10).contains(w));
+        }
+    }
+
+    let complete = erdos_renyi::<f64>(10, 1.0, 0.5..1.5, 1);
+    assert_eq!(90, complete.n_edges());
+    assert_eq!(0, erdos_renyi::<u32>(10, 0.0, 1..2, 1).n_edges());
+
+    let grid = grid_2d(3, 4, |node1, node2| (node1 + node2) as u32);
+    assert_eq!(12, grid.n_nodes());
+    assert_eq!(2 * 17, grid.n_edges());
+    assert_eq!(Some(&9), grid.edge_weight(4, 5));
+    assert_eq!(Some(&14), grid.edge_weight(9, 5));
+    assert!(!grid.has_edge(3, 4));
+
+    let rgg = random_geometric::<f64>(500, 0.1, 3);
+    assert_eq!(500, rgg.n_nodes());
+    assert_eq!(rgg.to_csr(), random_geometric::<f64>(500, 0.1, 3).to_csr());
+    assert!(rgg.n_edges() > 0);
+    for node in 0..500 {
+        for (_, w) in rgg.neighbours(node) {
+            assert!(*w > 0.0 && *w <= 0.1);
+        }
+    }
+}